@@ -0,0 +1,30 @@
+use criterion::{Criterion, Throughput, black_box, criterion_group, criterion_main};
+use serde::Deserialize;
+use tenor::{borrowed, search};
+
+/// A 50 result search response with all 15 formats.
+const SEARCH: &str = include_str!("fixtures/search.json");
+
+/// The owned model as the crate reads it, `next` becomes a cursor afterwards.
+#[derive(Deserialize)]
+#[allow(dead_code)]
+struct Owned {
+    results: Vec<search::ResponseObject>,
+    next: String,
+}
+
+fn deserialize(c: &mut Criterion) {
+    let mut group = c.benchmark_group("search_response");
+    group.throughput(Throughput::Bytes(SEARCH.len() as u64));
+
+    group.bench_function("owned", |b| {
+        b.iter(|| serde_json::from_str::<Owned>(black_box(SEARCH)).unwrap())
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| borrowed::Response::parse(black_box(SEARCH)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, deserialize);
+criterion_main!(benches);
@@ -0,0 +1,185 @@
+//! Borrowed versions of the [`search`] response types, for hot paths that parse many responses.
+//!
+//! Strings point into the body they were parsed from and are only copied when Tenor escaped a character in them.
+//! Fetch the body with [`Tenor::search_body`](crate::Tenor::search_body) or [`Tenor::featured_body`](crate::Tenor::featured_body),
+//! keep it around, and parse it with [`Response::parse`].
+
+use std::borrow::Cow;
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+use crate::{Error, MediaFilter, search};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Response<'a> {
+    #[serde(borrow)]
+    pub results: Vec<ResponseObject<'a>>,
+    /// The raw `pos` value for the next page, empty at the end.
+    #[serde(borrow)]
+    pub next: Cow<'a, str>,
+}
+
+impl<'a> Response<'a> {
+    /// Parses a search or featured response body.
+    pub fn parse(body: &'a str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(body)?)
+    }
+}
+
+/// Borrowed [`search::ResponseObject`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ResponseObject<'a> {
+    pub created: f64,
+    pub hasaudio: bool,
+    #[serde(borrow)]
+    pub id: Cow<'a, str>,
+    #[serde(borrow)]
+    pub media_formats: ContentFormats<'a>,
+    #[serde(borrow, deserialize_with = "borrowed_strs")]
+    pub tags: Vec<Cow<'a, str>>,
+    #[serde(borrow)]
+    pub title: Cow<'a, str>,
+    #[serde(borrow)]
+    pub content_description: Cow<'a, str>,
+    #[serde(borrow)]
+    pub itemurl: Cow<'a, str>,
+    pub flags: Vec<Value>,
+    #[serde(borrow)]
+    pub url: Cow<'a, str>,
+}
+
+impl ResponseObject<'_> {
+    /// Copies every field into the owned model.
+    pub fn into_owned(self) -> search::ResponseObject {
+        search::ResponseObject {
+            created: self.created,
+            hasaudio: self.hasaudio,
+            id: self.id.into_owned(),
+            media_formats: self.media_formats.into_owned(),
+            tags: self.tags.into_iter().map(Cow::into_owned).collect(),
+            title: self.title.into_owned(),
+            content_description: self.content_description.into_owned(),
+            itemurl: self.itemurl.into_owned(),
+            flags: self.flags,
+            url: self.url.into_owned(),
+        }
+    }
+}
+
+/// Borrowed [`search::ContentFormats`], every format shares the [`Format`] type.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ContentFormats<'a> {
+    #[serde(borrow)]
+    pub gifpreview: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub gif: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub mediumgif: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub tinygif: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub nanogif: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub mp4: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub loopedmp4: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub tinymp4: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub nanomp4: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub webm: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub tinywebm: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub nanowebm: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub webp: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub tinygifpreview: Option<Format<'a>>,
+    #[serde(borrow)]
+    pub nanogifpreview: Option<Format<'a>>,
+}
+
+impl<'a> ContentFormats<'a> {
+    /// Returns the entry matching the given [`MediaFilter`], like [`search::ContentFormats::get`].
+    pub fn get(&self, filter: &MediaFilter) -> Option<&Format<'a>> {
+        match filter {
+            MediaFilter::Preview => self.gifpreview.as_ref(),
+            MediaFilter::Gif => self.gif.as_ref(),
+            MediaFilter::MediumGif => self.mediumgif.as_ref(),
+            MediaFilter::TinyGif => self.tinygif.as_ref(),
+            MediaFilter::NanoGif => self.nanogif.as_ref(),
+            MediaFilter::Mp4 => self.mp4.as_ref(),
+            MediaFilter::LoopedMp4 => self.loopedmp4.as_ref(),
+            MediaFilter::TinyMp4 => self.tinymp4.as_ref(),
+            MediaFilter::NanoMp4 => self.nanomp4.as_ref(),
+            MediaFilter::Webm => self.webm.as_ref(),
+            MediaFilter::TinyWebm => self.tinywebm.as_ref(),
+            MediaFilter::NanoWebm => self.nanowebm.as_ref(),
+            MediaFilter::WebpTransparent => self.webp.as_ref(),
+            MediaFilter::TinyWebpTransparent
+            | MediaFilter::NanoWebpTransparent
+            | MediaFilter::GifTransparent
+            | MediaFilter::TinyGifTransparent
+            | MediaFilter::NanoGifTransparent => None,
+        }
+    }
+
+    fn into_owned(self) -> search::ContentFormats {
+        macro_rules! owned {
+            ($field:ident, $format:ident) => {
+                self.$field.map(|format| search::$format {
+                    url: format.url.into_owned(),
+                    duration: format.duration,
+                    preview: format.preview.into_owned(),
+                    dimensions: format.dimensions,
+                    size: format.size,
+                })
+            };
+        }
+
+        search::ContentFormats {
+            gifpreview: owned!(gifpreview, Gifpreview),
+            gif: owned!(gif, Gif),
+            mediumgif: owned!(mediumgif, Mediumgif),
+            tinygif: owned!(tinygif, Tinygif),
+            nanogif: owned!(nanogif, Nanogif),
+            mp4: owned!(mp4, Mp4),
+            loopedmp4: owned!(loopedmp4, Loopedmp4),
+            tinymp4: owned!(tinymp4, Tinymp4),
+            nanomp4: owned!(nanomp4, Nanomp4),
+            webm: owned!(webm, Webm),
+            tinywebm: owned!(tinywebm, Tinywebm),
+            nanowebm: owned!(nanowebm, Nanowebm),
+            webp: owned!(webp, Webp),
+            tinygifpreview: owned!(tinygifpreview, Tinygifpreview),
+            nanogifpreview: owned!(nanogifpreview, Nanogifpreview),
+        }
+    }
+}
+
+/// A single format, `dims` is usually `[width, height]` but is a list like in the owned model so both accept the same responses.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Format<'a> {
+    #[serde(borrow)]
+    pub url: Cow<'a, str>,
+    pub duration: f64,
+    #[serde(borrow)]
+    pub preview: Cow<'a, str>,
+    #[serde(rename = "dims")]
+    pub dimensions: Vec<i64>,
+    pub size: i64,
+}
+
+/// `Cow` only borrows as a direct field, so the list elements go through a wrapper.
+fn borrowed_strs<'de: 'a, 'a, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<Cow<'a, str>>, D::Error> {
+    #[derive(Deserialize)]
+    struct Borrowed<'a>(#[serde(borrow)] Cow<'a, str>);
+
+    let strs = Vec::<Borrowed<'a>>::deserialize(deserializer)?;
+    Ok(strs.into_iter().map(|Borrowed(str)| str).collect())
+}
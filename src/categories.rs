@@ -1,12 +1,100 @@
+use std::fmt;
+
 use iso_639::part1::Language;
 use serde::{Deserialize, Serialize};
 
-use crate::ContentFilter;
+use crate::{ContentFilter, CountryCode, Error, Locale, ValidationError, search};
+
+/// Which list of categories to fetch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Type {
+    #[default]
+    Featured,
+    Trending,
+}
+
+impl fmt::Display for Type {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Type::Featured => write!(f, "featured"),
+            Type::Trending => write!(f, "trending"),
+        }
+    }
+}
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Parameters {
     pub client_key: String,
     pub content_filter: ContentFilter,
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    pub locale: Option<Locale>,
+    /// Overrides the country of the locale for this request.
+    pub country: Option<CountryCode>,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self {
+            client_key: "my_test_app".to_string(),
+            content_filter: ContentFilter::default(),
+            locale: None,
+            country: None,
+        }
+    }
+}
+
+impl Parameters {
+    /// Returns a builder that validates the parameters when built.
+    pub fn builder() -> ParametersBuilder {
+        ParametersBuilder::default()
+    }
+
+    /// Checks the parameters against Tenor's documented constraints.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        ValidationError::check_client_key(&self.client_key)
+    }
+}
+
+/// Builder for [`Parameters`], starting from the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ParametersBuilder {
+    parms: Parameters,
+}
+
+impl ParametersBuilder {
+    #[must_use]
+    pub fn client_key(mut self, client_key: impl Into<String>) -> Self {
+        self.parms.client_key = client_key.into();
+        self
+    }
+
+    #[must_use]
+    pub fn content_filter(mut self, content_filter: ContentFilter) -> Self {
+        self.parms.content_filter = content_filter;
+        self
+    }
+
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    #[must_use]
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.parms.locale = Some(locale);
+        self
+    }
+
+    /// Overrides the country of the locale for this request.
+    #[must_use]
+    pub fn country(mut self, country: CountryCode) -> Self {
+        self.parms.country = Some(country);
+        self
+    }
+
+    /// Returns the parameters if they pass [`Parameters::validate`].
+    pub fn build(self) -> Result<Parameters, ValidationError> {
+        self.parms.validate()?;
+        Ok(self.parms)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -28,3 +116,11 @@ pub struct Tag {
     /// The name, example: "#excited".
     pub name: String,
 }
+
+/// A category along with the first page of its search results.
+#[derive(Debug, Clone)]
+pub struct Preview {
+    pub tag: Tag,
+    /// Results of searching for the tag's `searchterm`.
+    pub results: Result<search::Response, Error>,
+}
@@ -0,0 +1,89 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::ValidationError;
+
+/// The paginated endpoints a [`Cursor`] can belong to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Endpoint {
+    Search,
+    Featured,
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Endpoint::Search => write!(f, "search"),
+            Endpoint::Featured => write!(f, "featured"),
+        }
+    }
+}
+
+/// Position to continue a paginated listing from, taken from a response's `next` field.
+///
+/// It remembers which endpoint and query it came from so it can't continue a different listing.
+/// Serialize it to hand it to a client and deserialize it when the client asks for the next page.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Cursor {
+    endpoint: Endpoint,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    query: Option<String>,
+    #[serde(rename = "pos")]
+    position: String,
+}
+
+impl Cursor {
+    pub(crate) fn search(query: &str, position: String) -> Self {
+        Self {
+            endpoint: Endpoint::Search,
+            query: Some(query.to_string()),
+            position,
+        }
+    }
+
+    pub(crate) fn featured(position: String) -> Self {
+        Self {
+            endpoint: Endpoint::Featured,
+            query: None,
+            position,
+        }
+    }
+
+    pub fn endpoint(&self) -> Endpoint {
+        self.endpoint
+    }
+
+    /// The search query, `None` for featured.
+    pub fn query(&self) -> Option<&str> {
+        self.query.as_deref()
+    }
+
+    /// The raw `pos` value Tenor returned.
+    pub fn position(&self) -> &str {
+        &self.position
+    }
+
+    /// Returns whether there are no more pages.
+    pub fn is_end(&self) -> bool {
+        self.position.is_empty()
+    }
+
+    /// Returns the `pos` value if this cursor continues the given listing, any query is accepted when `query` is `None`.
+    pub(crate) fn position_for(
+        &self,
+        endpoint: Endpoint,
+        query: Option<&str>,
+    ) -> Result<&str, ValidationError> {
+        let same_query = query.is_none_or(|query| self.query() == Some(query));
+        if self.endpoint != endpoint || !same_query {
+            return Err(ValidationError::CursorMismatch(endpoint));
+        }
+
+        match self.is_end() {
+            true => Err(ValidationError::EndCursor),
+            false => Ok(&self.position),
+        }
+    }
+}
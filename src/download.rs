@@ -0,0 +1,264 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+    time::Duration,
+};
+
+use futures::{StreamExt, stream};
+use tokio::{
+    fs::File,
+    io::{AsyncWrite, AsyncWriteExt},
+};
+use tracing::{debug, error, warn};
+
+use crate::{Error, MediaFilter, RequestContext, search::ResponseObject, tenor::body_prefix};
+
+/// Download up to the specified number of files at once. Use this as the default if you are unsure.
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Retry a transient failure up to the specified number of times.
+pub const DEFAULT_RETRIES: u32 = 2;
+
+/// Delay before the first retry, doubled on every following attempt.
+const RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Downloads the file behind one format of many [`ResponseObject`]s.
+/// Created with [`Tenor::downloader`](crate::Tenor::downloader) so it shares the same client.
+#[derive(Debug, Clone)]
+pub struct Downloader {
+    client: reqwest::Client,
+    format: MediaFilter,
+    concurrency: usize,
+    retries: u32,
+}
+
+/// The result of downloading a single [`ResponseObject`].
+#[derive(Debug)]
+pub struct Download<T> {
+    /// Id of the [`ResponseObject`].
+    pub id: String,
+    /// The written file path or byte count, or why it failed.
+    pub result: Result<T, Error>,
+}
+
+impl Downloader {
+    pub(crate) fn new(client: reqwest::Client, format: MediaFilter) -> Self {
+        Self {
+            client,
+            format,
+            concurrency: DEFAULT_CONCURRENCY,
+            retries: DEFAULT_RETRIES,
+        }
+    }
+
+    /// Sets how many downloads run at once, zero is treated as one.
+    #[must_use]
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Sets how many times a transient failure is retried.
+    #[must_use]
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Downloads every object into `dir` as `<id>.<extension>`, returning one [`Download`] per object in order.
+    pub async fn to_dir(
+        &self,
+        objects: &[ResponseObject],
+        dir: impl AsRef<Path>,
+    ) -> Vec<Download<PathBuf>> {
+        let dir = dir.as_ref();
+
+        stream::iter(objects)
+            .map(|object| async move {
+                Download {
+                    id: object.id.clone(),
+                    result: self.to_file(object, dir).await,
+                }
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await
+    }
+
+    /// Downloads every object into its paired writer, returning one [`Download`] with the byte count per object in order.
+    ///
+    /// Once bytes have been written to a writer that download is no longer retried.
+    pub async fn to_writers<'a, W>(
+        &self,
+        objects: impl IntoIterator<Item = (&'a ResponseObject, W)>,
+    ) -> Vec<Download<u64>>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        stream::iter(objects)
+            .map(|(object, mut writer)| async move {
+                Download {
+                    id: object.id.clone(),
+                    result: self.to_writer(object, &mut writer).await,
+                }
+            })
+            .buffered(self.concurrency)
+            .collect()
+            .await
+    }
+
+    /// Downloads a single object into `writer`, returning the number of bytes written.
+    ///
+    /// Once bytes have been written the download is no longer retried.
+    pub async fn to_writer<W>(&self, object: &ResponseObject, writer: &mut W) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let (url, expected) = self.source(object)?;
+        let mut attempt = 0;
+
+        loop {
+            let mut written = 0;
+            match self.stream(url, expected, writer, &mut written).await {
+                Err(err) if written == 0 && attempt < self.retries && err.is_retryable() => {
+                    self.backoff(url, attempt).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    async fn to_file(&self, object: &ResponseObject, dir: &Path) -> Result<PathBuf, Error> {
+        let (url, expected) = self.source(object)?;
+        let path = dir.join(file_name(&object.id, url)?);
+        let mut attempt = 0;
+
+        loop {
+            let mut file = File::create(&path).await?;
+            match self.stream(url, expected, &mut file, &mut 0).await {
+                Ok(_) => return Ok(path),
+                Err(err) if attempt < self.retries && err.is_retryable() => {
+                    self.backoff(url, attempt).await;
+                    attempt += 1;
+                }
+                Err(err) => {
+                    drop(file);
+                    let _ = tokio::fs::remove_file(&path).await;
+                    return Err(err);
+                }
+            }
+        }
+    }
+
+    fn source<'a>(&self, object: &'a ResponseObject) -> Result<(&'a str, Option<u64>), Error> {
+        let media = object
+            .media_formats
+            .get(&self.format)
+            .ok_or_else(|| Error::MissingFormat {
+                id: object.id.clone(),
+                format: self.format,
+            })?;
+
+        Ok((
+            media.url,
+            u64::try_from(media.size).ok().filter(|&size| size > 0),
+        ))
+    }
+
+    async fn stream<W>(
+        &self,
+        url: &str,
+        expected: Option<u64>,
+        writer: &mut W,
+        written: &mut u64,
+    ) -> Result<u64, Error>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut context = RequestContext {
+            endpoint: "download".into(),
+            url: url.to_string(),
+            status: None,
+        };
+        let send_error = |context: &RequestContext, source: reqwest::Error| Error::Send {
+            context: context.clone(),
+            source: Arc::new(source.without_url()),
+        };
+
+        let mut response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .map_err(|source| send_error(&context, source))?;
+        let status = response.status();
+        context.status = Some(status);
+        if !status.is_success() {
+            error!("download of {url} failed with {status}.");
+            let body = response.text().await.unwrap_or_default();
+            return Err(Error::Status {
+                context,
+                body: body_prefix(&body),
+            });
+        }
+
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|source| send_error(&context, source))?
+        {
+            writer.write_all(&chunk).await?;
+            *written += chunk.len() as u64;
+        }
+        writer.flush().await?;
+
+        match expected {
+            Some(expected) if expected != *written => {
+                error!("download of {url} has the wrong size.");
+                Err(Error::SizeMismatch {
+                    expected,
+                    actual: *written,
+                })
+            }
+            _ => {
+                debug!("download of {url} successful.");
+                Ok(*written)
+            }
+        }
+    }
+
+    async fn backoff(&self, url: &str, attempt: u32) {
+        warn!("download of {url} failed, retrying.");
+        tokio::time::sleep(RETRY_DELAY * 2u32.pow(attempt)).await;
+    }
+}
+
+/// Builds `<id>.<extension>`, rejecting ids that could point outside the download directory.
+pub(crate) fn file_name(id: &str, url: &str) -> Result<String, Error> {
+    let safe = |name: &str| {
+        !name.is_empty()
+            && name
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_')
+    };
+    if !safe(id) {
+        return Err(Error::UnsafeId(id.to_string()));
+    }
+
+    Ok(format!("{id}.{}", extension(url)))
+}
+
+/// Takes the extension from the url's file name, Tenor always includes one.
+fn extension(url: &str) -> &str {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+
+    path.rsplit('/')
+        .next()
+        .and_then(|name| name.rsplit_once('.'))
+        .map(|(_, extension)| extension)
+        .filter(|extension| {
+            !extension.is_empty() && extension.bytes().all(|byte| byte.is_ascii_alphanumeric())
+        })
+        .unwrap_or("bin")
+}
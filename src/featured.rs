@@ -1,12 +1,126 @@
-use crate::{ArRange, ContentFilter, Limit, MediaFilter, SearchFilter};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug)]
+use crate::{
+    ArRange, ContentFilter, CountryCode, Cursor, DEFAULT_LIMIT, Endpoint, Limit, Locale,
+    MediaFilters, SearchFilter, ValidationError,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Parameters {
     pub client_key: String,
     pub search_filter: Option<SearchFilter>,
-    pub media_filter: Option<&'static [MediaFilter]>,
+    /// Formats to include in each result, `None` leaves the parameter out and Tenor returns every format.
+    /// This used to default to requesting every format in [`MediaFilter::ALL`](crate::MediaFilter::ALL).
+    pub media_filter: Option<MediaFilters>,
     pub ar_range: ArRange,
     pub content_filter: ContentFilter,
     pub limit: Limit,
-    pub position: Option<String>,
+    pub position: Option<Cursor>,
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    pub locale: Option<Locale>,
+    /// Overrides the country of the locale for this request.
+    pub country: Option<CountryCode>,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self {
+            client_key: "my_test_app".to_string(),
+            search_filter: None,
+            media_filter: None,
+            ar_range: ArRange::default(),
+            content_filter: ContentFilter::default(),
+            limit: DEFAULT_LIMIT,
+            position: None,
+            locale: None,
+            country: None,
+        }
+    }
+}
+
+impl Parameters {
+    /// Returns a builder that validates the parameters when built.
+    pub fn builder() -> ParametersBuilder {
+        ParametersBuilder::default()
+    }
+
+    /// Checks the parameters against Tenor's documented constraints.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        ValidationError::check_client_key(&self.client_key)?;
+        ValidationError::check_limit(self.limit)?;
+        ValidationError::check_position(self.position.as_ref(), Endpoint::Featured)?;
+        ValidationError::check_media_filter(self.search_filter, self.media_filter)
+    }
+}
+
+/// Builder for [`Parameters`], starting from the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ParametersBuilder {
+    parms: Parameters,
+}
+
+impl ParametersBuilder {
+    #[must_use]
+    pub fn client_key(mut self, client_key: impl Into<String>) -> Self {
+        self.parms.client_key = client_key.into();
+        self
+    }
+
+    #[must_use]
+    pub fn search_filter(mut self, search_filter: SearchFilter) -> Self {
+        self.parms.search_filter = Some(search_filter);
+        self
+    }
+
+    #[must_use]
+    pub fn media_filter(mut self, media_filter: impl Into<MediaFilters>) -> Self {
+        self.parms.media_filter = Some(media_filter.into());
+        self
+    }
+
+    #[must_use]
+    pub fn ar_range(mut self, ar_range: ArRange) -> Self {
+        self.parms.ar_range = ar_range;
+        self
+    }
+
+    #[must_use]
+    pub fn content_filter(mut self, content_filter: ContentFilter) -> Self {
+        self.parms.content_filter = content_filter;
+        self
+    }
+
+    #[must_use]
+    pub fn limit(mut self, limit: Limit) -> Self {
+        self.parms.limit = limit;
+        self
+    }
+
+    /// Continue from the `next` cursor of a previous response.
+    #[must_use]
+    pub fn position(mut self, position: Cursor) -> Self {
+        self.parms.position = Some(position);
+        self
+    }
+
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    #[must_use]
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.parms.locale = Some(locale);
+        self
+    }
+
+    /// Overrides the country of the locale for this request.
+    #[must_use]
+    pub fn country(mut self, country: CountryCode) -> Self {
+        self.parms.country = Some(country);
+        self
+    }
+
+    /// Returns the parameters if they pass [`Parameters::validate`].
+    pub fn build(self) -> Result<Parameters, ValidationError> {
+        self.parms.validate()?;
+        Ok(self.parms)
+    }
 }
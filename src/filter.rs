@@ -0,0 +1,167 @@
+use std::time::Duration;
+
+use crate::{
+    MediaFilter, MediaFilters,
+    search::{Media, ResponseObject},
+};
+
+/// Client-side filter over [`ResponseObject`]s for properties Tenor can't filter on.
+/// Every condition that is set must hold, an empty filter keeps everything.
+///
+/// Aspect ratio is read from the first format Tenor included, in the order of [`MediaFilter::ALL`].
+/// Duration is read the same way, skipping the still `gifpreview` and the repeating `loopedmp4`.
+/// Pass it to [`Tenor::search_n_filtered`](crate::Tenor::search_n_filtered) to keep fetching pages until enough results match.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ResultFilter {
+    has_audio: Option<bool>,
+    min_aspect_ratio: Option<f64>,
+    max_aspect_ratio: Option<f64>,
+    min_duration: Option<Duration>,
+    max_duration: Option<Duration>,
+    max_size: Vec<(MediaFilter, u64)>,
+    required: MediaFilters,
+}
+
+impl ResultFilter {
+    /// Creates a filter that keeps everything.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Keeps only results with or without audio.
+    #[must_use]
+    pub fn has_audio(mut self, has_audio: bool) -> Self {
+        self.has_audio = Some(has_audio);
+        self
+    }
+
+    /// Keeps only results at least this wide, as width divided by height.
+    #[must_use]
+    pub fn min_aspect_ratio(mut self, ratio: f64) -> Self {
+        self.min_aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Keeps only results at most this wide, as width divided by height.
+    #[must_use]
+    pub fn max_aspect_ratio(mut self, ratio: f64) -> Self {
+        self.max_aspect_ratio = Some(ratio);
+        self
+    }
+
+    /// Keeps only results lasting at least this long, stills have a duration of zero.
+    #[must_use]
+    pub fn min_duration(mut self, duration: Duration) -> Self {
+        self.min_duration = Some(duration);
+        self
+    }
+
+    /// Keeps only results lasting at most this long.
+    #[must_use]
+    pub fn max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    /// Keeps only results whose `format` file is at most `bytes` large, results without the format are dropped.
+    #[must_use]
+    pub fn max_size(mut self, format: MediaFilter, bytes: u64) -> Self {
+        self.max_size.retain(|(existing, _)| *existing != format);
+        self.max_size.push((format, bytes));
+        self
+    }
+
+    /// Keeps only results that include every one of the formats.
+    #[must_use]
+    pub fn require(mut self, formats: impl Into<MediaFilters>) -> Self {
+        self.required |= formats.into();
+        self
+    }
+
+    /// Returns whether the result passes every condition.
+    pub fn matches(&self, object: &ResponseObject) -> bool {
+        if self
+            .has_audio
+            .is_some_and(|has_audio| object.hasaudio != has_audio)
+        {
+            return false;
+        }
+
+        let formats = &object.media_formats;
+        if self
+            .required
+            .iter()
+            .any(|format| formats.get(&format).is_none())
+        {
+            return false;
+        }
+
+        let fits = self.max_size.iter().all(|(format, bytes)| {
+            formats
+                .get(format)
+                .is_some_and(|media| u64::try_from(media.size).is_ok_and(|size| size <= *bytes))
+        });
+        if !fits {
+            return false;
+        }
+
+        if self.min_aspect_ratio.is_some() || self.max_aspect_ratio.is_some() {
+            let Some(ratio) = first_media(object).and_then(aspect_ratio) else {
+                return false;
+            };
+            if self.min_aspect_ratio.is_some_and(|min| ratio < min)
+                || self.max_aspect_ratio.is_some_and(|max| ratio > max)
+            {
+                return false;
+            }
+        }
+
+        if self.min_duration.is_some() || self.max_duration.is_some() {
+            let Some(duration) = clip_duration(object) else {
+                return false;
+            };
+            if self
+                .min_duration
+                .is_some_and(|min| duration < min.as_secs_f64())
+                || self
+                    .max_duration
+                    .is_some_and(|max| duration > max.as_secs_f64())
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Keeps the matching results, in order.
+    pub fn apply(&self, mut objects: Vec<ResponseObject>) -> Vec<ResponseObject> {
+        objects.retain(|object| self.matches(object));
+        objects
+    }
+}
+
+fn first_media(object: &ResponseObject) -> Option<Media<'_>> {
+    MediaFilter::ALL
+        .iter()
+        .find_map(|format| object.media_formats.get(format))
+}
+
+/// Length of one play, skipping the still `gifpreview` and `loopedmp4`, which repeats the clip a few times.
+/// Results with nothing else fall back to the first format.
+fn clip_duration(object: &ResponseObject) -> Option<f64> {
+    MediaFilter::ALL
+        .iter()
+        .filter(|format| !matches!(format, MediaFilter::Preview | MediaFilter::LoopedMp4))
+        .find_map(|format| object.media_formats.get(format))
+        .or_else(|| first_media(object))
+        .map(|media| media.duration)
+}
+
+/// Width divided by height, `dims` is `[width, height]`.
+fn aspect_ratio(media: Media<'_>) -> Option<f64> {
+    match media.dimensions {
+        [width, height, ..] if *width > 0 && *height > 0 => Some(*width as f64 / *height as f64),
+        _ => None,
+    }
+}
@@ -1,18 +1,45 @@
+pub mod borrowed;
 pub mod categories;
+mod cursor;
+pub mod download;
 pub mod featured;
+pub mod filter;
+mod locale;
+pub mod moderation;
+#[cfg(feature = "presets")]
+pub mod preset;
+pub mod provider;
+pub mod random;
 pub mod search;
 #[doc(hidden)]
 pub mod tenor;
+#[cfg(feature = "test-util")]
+pub mod testing;
+mod tracked;
+pub mod transport;
 pub mod trending;
 
 #[cfg(test)]
 mod tests;
 
 pub use codes_iso_3166::part_1::CountryCode;
-use iso_639::part1::Language;
+pub use cursor::{Cursor, Endpoint};
+pub use iso_639::part1::Language;
+pub use locale::{Locale, LocaleError};
+pub use provider::GifProvider;
+pub use reqwest::{StatusCode, header::HeaderMap};
 pub use tenor::Tenor;
+pub use tracked::{ResponseMeta, Tracked};
 
-use std::{fmt, sync::Arc};
+use std::{
+    borrow::Cow,
+    fmt,
+    ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not, Sub, SubAssign},
+    str::FromStr,
+    sync::Arc,
+};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
 pub type Limit = u8;
@@ -26,10 +53,221 @@ pub const MAX_LIMIT: Limit = 50;
 /// Error.
 #[derive(Error, Debug, Clone)]
 pub enum Error {
+    /// Legacy, only built by the `From<reqwest::Error>` impl. Requests sent by this crate,
+    /// api calls and downloads alike, fail with [`Error::Send`] or [`Error::Status`] instead.
     #[error(transparent)]
     Request(Arc<reqwest::Error>),
+    /// Json that isn't an api response, such as a cassette or [`borrowed::Response::parse`], could not be read or written.
+    /// Api responses that can't be decoded fail with [`Error::Decode`].
     #[error(transparent)]
     Serialization(Arc<serde_json::Error>),
+    #[error(transparent)]
+    Io(Arc<std::io::Error>),
+    #[error("{id} has no {format} format")]
+    MissingFormat { id: String, format: MediaFilter },
+    /// The id can't be used as a file name, only ascii letters, digits, `-` and `_` are allowed.
+    #[error("`{0}` is not a safe file name")]
+    UnsafeId(String),
+    #[error("expected {expected} bytes, got {actual}")]
+    SizeMismatch { expected: u64, actual: u64 },
+    #[error(transparent)]
+    Validation(ValidationError),
+    #[error("`{0}` is blocked")]
+    BlockedQuery(String),
+    /// The request could not be sent or its body could not be read.
+    #[error("{context} failed: {source}")]
+    Send {
+        context: RequestContext,
+        source: Arc<reqwest::Error>,
+    },
+    /// Tenor answered with an error status, `body` holds the start of its explanation.
+    #[error("{context}: {body}")]
+    Status {
+        context: RequestContext,
+        body: String,
+    },
+    /// The response wasn't the expected json, `body` holds its start.
+    #[error("{context} could not be decoded: {source}")]
+    Decode {
+        context: RequestContext,
+        body: String,
+        source: Arc<serde_json::Error>,
+    },
+    /// A custom transport failed, for example a timeout or load shedding layer.
+    #[error("{context} failed: {source}")]
+    Transport {
+        context: RequestContext,
+        source: Arc<dyn std::error::Error + Send + Sync>,
+    },
+    /// A replaying cassette from the `test-util` feature has no recording for the request, or the recording is broken.
+    /// Trying again can't help, so this is never retryable.
+    #[error("{context} could not be replayed: {message}")]
+    Replay {
+        context: RequestContext,
+        message: String,
+    },
+}
+
+impl Error {
+    /// Where the failed api request was sent, if it got that far.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            Error::Send { context, .. }
+            | Error::Status { context, .. }
+            | Error::Decode { context, .. }
+            | Error::Transport { context, .. }
+            | Error::Replay { context, .. } => Some(context),
+            _ => None,
+        }
+    }
+
+    /// The http status of the failed request, if a response came back.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Error::Request(err) => err.status(),
+            _ => self.context().and_then(|context| context.status),
+        }
+    }
+
+    /// Timeouts, dropped connections, rate limits and server errors are worth retrying,
+    /// so are failures of a custom transport since middleware such as load shedding fails this way.
+    pub fn is_retryable(&self) -> bool {
+        if let Some(status) = self.status() {
+            return status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS;
+        }
+
+        match self {
+            Error::Request(err) | Error::Send { source: err, .. } => {
+                err.is_timeout() || err.is_connect() || err.is_body()
+            }
+            Error::Transport { .. } => true,
+            _ => false,
+        }
+    }
+
+    /// The api key is missing, invalid or not allowed to use Tenor.
+    pub fn is_auth(&self) -> bool {
+        match (self.status(), self) {
+            (Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN), _) => true,
+            // Google reports a bad key as a bad request.
+            (Some(StatusCode::BAD_REQUEST), Error::Status { body, .. }) => {
+                body.contains("API_KEY_INVALID") || body.contains("API key not valid")
+            }
+            _ => false,
+        }
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+}
+
+/// Which request failed, the url never includes the api key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    /// Name of the endpoint, for example `search`, or `download` for media files.
+    pub endpoint: Cow<'static, str>,
+    /// The request url with the api key replaced by `REDACTED`.
+    pub url: String,
+    /// The http status, if a response came back.
+    pub status: Option<StatusCode>,
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` request to {}", self.endpoint, self.url)?;
+        if let Some(status) = self.status {
+            write!(f, " returned {status}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Error returned when parsing one of the request enums from its wire name fails.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("`{value}` is not a valid {kind}")]
+pub struct ParseError {
+    kind: &'static str,
+    value: String,
+}
+
+impl ParseError {
+    pub(crate) fn new(kind: &'static str, value: &str) -> Self {
+        Self {
+            kind,
+            value: value.to_string(),
+        }
+    }
+}
+
+/// Error returned when parameters break one of Tenor's documented constraints.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    #[error("limit must be between 1 and {MAX_LIMIT}, got {0}")]
+    Limit(Limit),
+    #[error("client_key must not be empty")]
+    EmptyClientKey,
+    #[error("media_filter must not be empty, leave it unset to get every format")]
+    EmptyMediaFilter,
+    #[error("{0} are only available for stickers, set a search_filter")]
+    TransparentWithoutSticker(MediaFilters),
+    #[error("the cursor is at the end of the listing, there are no more pages")]
+    EndCursor,
+    #[error("the cursor does not continue this {0} listing")]
+    CursorMismatch(Endpoint),
+}
+
+impl ValidationError {
+    pub(crate) fn check_limit(limit: Limit) -> Result<(), ValidationError> {
+        match limit {
+            1..=MAX_LIMIT => Ok(()),
+            _ => Err(ValidationError::Limit(limit)),
+        }
+    }
+
+    pub(crate) fn check_client_key(client_key: &str) -> Result<(), ValidationError> {
+        match client_key.is_empty() {
+            true => Err(ValidationError::EmptyClientKey),
+            false => Ok(()),
+        }
+    }
+
+    pub(crate) fn check_position(
+        position: Option<&Cursor>,
+        endpoint: Endpoint,
+    ) -> Result<(), ValidationError> {
+        match position {
+            Some(cursor) => cursor.position_for(endpoint, None).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+
+    /// Transparent formats only exist for sticker content, so GIF searches can't ask for them.
+    pub(crate) fn check_media_filter(
+        search_filter: Option<SearchFilter>,
+        media_filter: Option<MediaFilters>,
+    ) -> Result<(), ValidationError> {
+        let Some(media_filter) = media_filter else {
+            return Ok(());
+        };
+
+        if media_filter.is_empty() {
+            return Err(ValidationError::EmptyMediaFilter);
+        }
+
+        let transparent = media_filter & MediaFilters::TRANSPARENT;
+        match search_filter {
+            None if !transparent.is_empty() => {
+                Err(ValidationError::TransparentWithoutSticker(transparent))
+            }
+            _ => Ok(()),
+        }
+    }
 }
 
 impl From<reqwest::Error> for Error {
@@ -44,6 +282,55 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<ValidationError> for Error {
+    fn from(value: ValidationError) -> Self {
+        Error::Validation(value)
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Error::Io(Arc::new(value))
+    }
+}
+
+/// Implements `Display`, `FromStr` and serde for a request enum using the names Tenor puts on the wire.
+macro_rules! wire_names {
+    ($type:ident, $kind:literal, { $($variant:ident => $name:literal),+ $(,)? }) => {
+        impl fmt::Display for $type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(match self {
+                    $($type::$variant => $name,)+
+                })
+            }
+        }
+
+        impl FromStr for $type {
+            type Err = ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                match s {
+                    $($name => Ok($type::$variant),)+
+                    _ => Err(ParseError::new($kind, s)),
+                }
+            }
+        }
+
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let name = String::deserialize(deserializer)?;
+                name.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
 /// Filter the Response Objects to only include GIFs with aspect ratios that fit within the selected range.
 /// The default value is all. The accepted values are all, wide, and standard:
 ///
@@ -51,7 +338,7 @@ impl From<serde_json::Error> for Error {
 /// wide: 0.42 <= aspect ratio <= 2.36
 /// standard: 0.56 <= aspect ratio <= 1.78
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum ArRange {
     #[default]
     All,
@@ -60,21 +347,20 @@ pub enum ArRange {
 }
 
 impl ArRange {
-    pub(crate) fn to_query_parameter(&self) -> String {
-        format!(
-            "&ar_range={}",
-            match self {
-                ArRange::All => "all",
-                ArRange::Wide => "wide",
-                ArRange::Standard => "standard",
-            }
-        )
+    pub(crate) fn to_query_parameter(self) -> String {
+        format!("&ar_range={self}")
     }
 }
 
+wire_names!(ArRange, "aspect ratio range", {
+    All => "all",
+    Wide => "wide",
+    Standard => "standard",
+});
+
 /// Specify the content safety filter level.
 /// The default value is off. The accepted values are off, low, medium, and high.
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
 pub enum ContentFilter {
     #[default]
     Off,
@@ -84,66 +370,22 @@ pub enum ContentFilter {
 }
 
 impl ContentFilter {
-    pub(crate) fn to_query_parameter(&self) -> String {
-        format!(
-            "&contentfilter={}",
-            match self {
-                ContentFilter::Off => "off",
-                ContentFilter::Low => "low",
-                ContentFilter::Medium => "medium",
-                ContentFilter::High => "high",
-            }
-        )
-    }
-}
-
-/// Specify the default language to interpret the search string. xx is the language's ISO 639-1 language code, while the optional YY value is the two-letter ISO 3166-1 country code.
-/// You can use the country code that you provide in locale to differentiate between dialects of the given language.
-/// The default value is en_US.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Locale {
-    language: Language,
-    country: CountryCode,
-}
-
-impl Locale {
-    pub fn new(language: Language, country: CountryCode) -> Self {
-        Self { language, country }
-    }
-
-    pub(crate) fn to_query_parameter(&self) -> String {
-        format!(
-            "&country={}&locate={}",
-            self.country.to_string().to_uppercase(),
-            self.to_string()
-        )
-    }
-}
-
-impl Default for Locale {
-    fn default() -> Self {
-        Self {
-            language: Language::English,
-            country: CountryCode::US,
-        }
+    pub(crate) fn to_query_parameter(self) -> String {
+        format!("&contentfilter={self}")
     }
 }
 
-impl fmt::Display for Locale {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "{}_{}",
-            self.language.to_string().to_lowercase(),
-            self.country.to_string().to_uppercase(),
-        )
-    }
-}
+wire_names!(ContentFilter, "content filter", {
+    Off => "off",
+    Low => "low",
+    Medium => "medium",
+    High => "high",
+});
 
 /// Comma-separated list of GIF formats to filter the Response Objects. By default, media_filter returns all formats for each Response Object.
 /// Example: media_filter=gif,tinygif,mp4,tinymp4
 /// Doesn't have a default value.
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum MediaFilter {
     Preview,
     Gif,
@@ -187,32 +429,243 @@ impl MediaFilter {
         MediaFilter::NanoGifTransparent,
     ];
 
+    #[deprecated(note = "use `MediaFilters::ALL`")]
     pub const DEFAULT: &'static [MediaFilter] = Self::ALL;
+
+    const fn bit(self) -> u32 {
+        1 << self as u32
+    }
+}
+
+wire_names!(MediaFilter, "media filter", {
+    Preview => "preview",
+    Gif => "gif",
+    MediumGif => "mediumgif",
+    TinyGif => "tinygif",
+    NanoGif => "nanogif",
+    Mp4 => "mp4",
+    LoopedMp4 => "loopedmp4",
+    TinyMp4 => "tinymp4",
+    NanoMp4 => "nanomp4",
+    Webm => "webm",
+    TinyWebm => "tinywebm",
+    NanoWebm => "nanowebm",
+    WebpTransparent => "webp_transparent",
+    TinyWebpTransparent => "tinywebp_transparent",
+    NanoWebpTransparent => "nanowebp_transparent",
+    GifTransparent => "gif_transparent",
+    TinyGifTransparent => "tinygif_transparent",
+    NanoGifTransparent => "nanogif_transparent",
+});
+
+impl BitOr for MediaFilter {
+    type Output = MediaFilters;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        MediaFilters::from(self) | rhs
+    }
+}
+
+/// Deduplicated set of [`MediaFilter`]s, sent as the comma-separated `media_filter` parameter.
+///
+/// Build one from an iterator, a string such as `gif,tinygif,mp4` or by combining filters with `|`.
+/// The formats are always listed in the order of [`MediaFilter::ALL`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct MediaFilters(u32);
+
+impl MediaFilters {
+    pub const EMPTY: MediaFilters = MediaFilters(0);
+
+    pub const ALL: MediaFilters = MediaFilters((1 << MediaFilter::ALL.len()) - 1);
+
+    /// Every format a GIF search can return, [`MediaFilters::ALL`] without the sticker only [`MediaFilters::TRANSPARENT`] formats.
+    pub const DEFAULT: MediaFilters = MediaFilters(Self::ALL.0 & !Self::TRANSPARENT.0);
+
+    /// The sticker only formats.
+    pub const TRANSPARENT: MediaFilters = MediaFilters(
+        MediaFilter::WebpTransparent.bit()
+            | MediaFilter::TinyWebpTransparent.bit()
+            | MediaFilter::NanoWebpTransparent.bit()
+            | MediaFilter::GifTransparent.bit()
+            | MediaFilter::TinyGifTransparent.bit()
+            | MediaFilter::NanoGifTransparent.bit(),
+    );
+
+    pub const fn contains(&self, filter: MediaFilter) -> bool {
+        self.0 & filter.bit() != 0
+    }
+
+    /// Returns whether the filter was newly inserted.
+    pub fn insert(&mut self, filter: MediaFilter) -> bool {
+        let inserted = !self.contains(filter);
+        self.0 |= filter.bit();
+        inserted
+    }
+
+    /// Returns whether the filter was present.
+    pub fn remove(&mut self, filter: MediaFilter) -> bool {
+        let removed = self.contains(filter);
+        self.0 &= !filter.bit();
+        removed
+    }
+
+    pub const fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub const fn union(self, other: MediaFilters) -> MediaFilters {
+        MediaFilters(self.0 | other.0)
+    }
+
+    pub const fn intersection(self, other: MediaFilters) -> MediaFilters {
+        MediaFilters(self.0 & other.0)
+    }
+
+    pub const fn difference(self, other: MediaFilters) -> MediaFilters {
+        MediaFilters(self.0 & !other.0)
+    }
+
+    pub const fn symmetric_difference(self, other: MediaFilters) -> MediaFilters {
+        MediaFilters(self.0 ^ other.0)
+    }
+
+    pub const fn is_subset(&self, other: &MediaFilters) -> bool {
+        self.0 & !other.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = MediaFilter> + '_ {
+        MediaFilter::ALL
+            .iter()
+            .copied()
+            .filter(|filter| self.contains(*filter))
+    }
+
+    pub(crate) fn to_query_parameter(self) -> String {
+        format!("&media_filter={self}")
+    }
+}
+
+impl From<MediaFilter> for MediaFilters {
+    fn from(value: MediaFilter) -> Self {
+        MediaFilters(value.bit())
+    }
+}
+
+impl<const N: usize> From<[MediaFilter; N]> for MediaFilters {
+    fn from(value: [MediaFilter; N]) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl From<&[MediaFilter]> for MediaFilters {
+    fn from(value: &[MediaFilter]) -> Self {
+        value.iter().copied().collect()
+    }
+}
+
+impl FromIterator<MediaFilter> for MediaFilters {
+    fn from_iter<T: IntoIterator<Item = MediaFilter>>(iter: T) -> Self {
+        let mut filters = MediaFilters::EMPTY;
+        filters.extend(iter);
+        filters
+    }
+}
+
+impl Extend<MediaFilter> for MediaFilters {
+    fn extend<T: IntoIterator<Item = MediaFilter>>(&mut self, iter: T) {
+        iter.into_iter().for_each(|filter| {
+            self.insert(filter);
+        });
+    }
+}
+
+impl IntoIterator for MediaFilters {
+    type Item = MediaFilter;
+    type IntoIter = std::vec::IntoIter<MediaFilter>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter().collect::<Vec<_>>().into_iter()
+    }
+}
+
+macro_rules! media_filters_operator {
+    ($trait:ident, $method:ident, $assign_trait:ident, $assign_method:ident, $set_method:ident) => {
+        impl<T: Into<MediaFilters>> $trait<T> for MediaFilters {
+            type Output = MediaFilters;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                self.$set_method(rhs.into())
+            }
+        }
+
+        impl<T: Into<MediaFilters>> $assign_trait<T> for MediaFilters {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = self.$set_method(rhs.into());
+            }
+        }
+    };
+}
+
+media_filters_operator!(BitOr, bitor, BitOrAssign, bitor_assign, union);
+media_filters_operator!(BitAnd, bitand, BitAndAssign, bitand_assign, intersection);
+media_filters_operator!(Sub, sub, SubAssign, sub_assign, difference);
+media_filters_operator!(
+    BitXor,
+    bitxor,
+    BitXorAssign,
+    bitxor_assign,
+    symmetric_difference
+);
+
+impl Not for MediaFilters {
+    type Output = MediaFilters;
+
+    fn not(self) -> Self::Output {
+        MediaFilters::ALL.difference(self)
+    }
 }
 
-// This is just so we get `to_string()` I don't care about `Display`.
-impl fmt::Display for MediaFilter {
+/// The wire form, for example `gif,tinygif,mp4` without a trailing comma.
+impl fmt::Display for MediaFilters {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            MediaFilter::Preview => write!(f, "preview"),
-            MediaFilter::Gif => write!(f, "gif"),
-            MediaFilter::MediumGif => write!(f, "mediumgif"),
-            MediaFilter::TinyGif => write!(f, "tinygif"),
-            MediaFilter::NanoGif => write!(f, "nanogif"),
-            MediaFilter::Mp4 => write!(f, "mp4"),
-            MediaFilter::LoopedMp4 => write!(f, "loopedmp4"),
-            MediaFilter::TinyMp4 => write!(f, "tinymp4"),
-            MediaFilter::NanoMp4 => write!(f, "nanomp4"),
-            MediaFilter::Webm => write!(f, "webm"),
-            MediaFilter::TinyWebm => write!(f, "tinywebm"),
-            MediaFilter::NanoWebm => write!(f, "nanowebm"),
-            MediaFilter::WebpTransparent => write!(f, "webp_transparent"),
-            MediaFilter::TinyWebpTransparent => write!(f, "tinywebp_transparent"),
-            MediaFilter::NanoWebpTransparent => write!(f, "nanowebp_transparent"),
-            MediaFilter::GifTransparent => write!(f, "gif_transparent"),
-            MediaFilter::TinyGifTransparent => write!(f, "tinygif_transparent"),
-            MediaFilter::NanoGifTransparent => write!(f, "nanogif_transparent"),
+        for (i, filter) in self.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+            write!(f, "{filter}")?;
         }
+
+        Ok(())
+    }
+}
+
+/// Accepts the wire form, whitespace around the names and empty entries are ignored.
+impl FromStr for MediaFilters {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split(',')
+            .map(str::trim)
+            .filter(|filter| !filter.is_empty())
+            .map(MediaFilter::from_str)
+            .collect()
+    }
+}
+
+impl Serialize for MediaFilters {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaFilters {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let filters = String::deserialize(deserializer)?;
+        filters.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -225,7 +678,7 @@ impl fmt::Display for MediaFilter {
 ///
 /// For GIF content, either leave searchfilter blank or don't use it.
 ///
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum SearchFilter {
     Sticker,
     Static,
@@ -233,14 +686,13 @@ pub enum SearchFilter {
 }
 
 impl SearchFilter {
-    pub(crate) fn to_query_parameter(&self) -> String {
-        format!(
-            "&searchfilter={}",
-            match self {
-                SearchFilter::Sticker => "sticker",
-                SearchFilter::Static => "sticker,static",
-                SearchFilter::NonStatic => "sticker,-static",
-            }
-        )
+    pub(crate) fn to_query_parameter(self) -> String {
+        format!("&searchfilter={self}")
     }
 }
+
+wire_names!(SearchFilter, "search filter", {
+    Sticker => "sticker",
+    Static => "sticker,static",
+    NonStatic => "sticker,-static",
+});
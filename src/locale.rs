@@ -0,0 +1,175 @@
+use std::{fmt, str::FromStr};
+
+use iso_639::part1::Language;
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de::IntoDeserializer};
+use thiserror::Error;
+
+use crate::CountryCode;
+
+/// Specify the default language to interpret the search string. xx is the language's ISO 639-1 language code, while the optional YY value is the two-letter ISO 3166-1 country code.
+/// You can use the country code that you provide in locale to differentiate between dialects of the given language.
+/// The default value is en_US.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    language: Language,
+    country: Option<CountryCode>,
+}
+
+/// Error returned when parsing a [`Locale`] fails.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum LocaleError {
+    #[error("locale is empty")]
+    Empty,
+    #[error("`{0}` is not an ISO 639-1 language code")]
+    InvalidLanguage(String),
+    #[error("`{0}` is not an ISO 3166-1 country code")]
+    InvalidCountry(String),
+    #[error("`{0}` is not a locale, expected xx_YY, xx-YY or xx")]
+    Malformed(String),
+}
+
+impl Locale {
+    pub fn new(language: Language, country: CountryCode) -> Self {
+        Self {
+            language,
+            country: Some(country),
+        }
+    }
+
+    /// Creates a locale without a country, Tenor then picks the dialect.
+    pub fn from_language(language: Language) -> Self {
+        Self {
+            language,
+            country: None,
+        }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    pub fn country(&self) -> Option<CountryCode> {
+        self.country
+    }
+
+    /// Picks the best of the `supported` locales for an `Accept-Language` header such as `pt-BR,pt;q=0.9,en;q=0.8`.
+    ///
+    /// Languages are tried from the highest to the lowest quality, each one first matching a supported locale exactly,
+    /// then falling back to any supported locale with the same language. A `*` matches the first supported locale.
+    pub fn negotiate(accept_language: &str, supported: &[Locale]) -> Option<Locale> {
+        let mut ranges: Vec<(&str, f32)> = accept_language
+            .split(',')
+            .filter_map(|range| {
+                let mut parts = range.split(';').map(str::trim);
+                let tag = parts.next().filter(|tag| !tag.is_empty())?;
+                let quality = parts
+                    .find_map(|parameter| parameter.strip_prefix("q="))
+                    .map_or(Some(1.0), |quality| quality.parse().ok())?;
+
+                (quality > 0.0).then_some((tag, quality))
+            })
+            .collect();
+        // Stable, so equal qualities keep the header's order.
+        ranges.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        ranges.into_iter().find_map(|(tag, _)| {
+            if tag == "*" {
+                return supported.first().copied();
+            }
+
+            let wanted: Locale = tag.parse().ok()?;
+            supported
+                .iter()
+                .find(|locale| **locale == wanted)
+                .or_else(|| {
+                    supported
+                        .iter()
+                        .find(|locale| locale.language == wanted.language)
+                })
+                .copied()
+        })
+    }
+
+    /// The `country` overrides the locale's own country for the `country` parameter only.
+    pub(crate) fn to_query_parameter(self, country: Option<CountryCode>) -> String {
+        let mut parameter = String::new();
+        if let Some(country) = country.or(self.country) {
+            parameter.push_str(&format!("&country={}", country.to_string().to_uppercase()));
+        }
+        parameter.push_str(&format!("&locale={self}"));
+
+        parameter
+    }
+}
+
+impl Default for Locale {
+    fn default() -> Self {
+        Self::new(Language::English, CountryCode::US)
+    }
+}
+
+impl fmt::Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.language.to_string().to_lowercase())?;
+        if let Some(country) = self.country {
+            write!(f, "_{}", country.to_string().to_uppercase())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Accepts `xx_YY`, `xx-YY` and language only `xx`, in any case.
+/// Script subtags such as the `Hant` in `zh-Hant-TW` are skipped.
+impl FromStr for Locale {
+    type Err = LocaleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if s.is_empty() {
+            return Err(LocaleError::Empty);
+        }
+
+        let mut subtags = s.split(['_', '-']);
+        let language = subtags.next().unwrap_or_default();
+        if language.len() != 2 {
+            return Err(LocaleError::Malformed(s.to_string()));
+        }
+        // Goes through serde since that is how Tenor's own `locale` fields are read.
+        let parsed: Result<Language, serde::de::value::Error> =
+            Language::deserialize(language.to_lowercase().into_deserializer());
+        let language = parsed.map_err(|_| LocaleError::InvalidLanguage(language.to_string()))?;
+
+        let mut country = None;
+        for subtag in subtags {
+            match subtag.len() {
+                4 if country.is_none() => continue,
+                2 if country.is_none() => {
+                    country = Some(
+                        subtag
+                            .to_uppercase()
+                            .parse::<CountryCode>()
+                            .map_err(|_| LocaleError::InvalidCountry(subtag.to_string()))?,
+                    );
+                }
+                _ => return Err(LocaleError::Malformed(s.to_string())),
+            }
+        }
+
+        Ok(Self { language, country })
+    }
+}
+
+/// Serialized as the string form, for example `ja_JP`.
+impl Serialize for Locale {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Locale {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let locale = String::deserialize(deserializer)?;
+        locale.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,289 @@
+use std::process::ExitCode;
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use tenor::{
+    ArRange, ContentFilter, CountryCode, Cursor, DEFAULT_LIMIT, Endpoint, Limit, Locale, MAX_LIMIT,
+    MediaFilters, SearchFilter, Tenor, categories, featured, search, trending,
+};
+
+/// Explore the Tenor api from the command line.
+#[derive(Debug, Parser)]
+#[command(name = "tenor", version)]
+struct Cli {
+    /// Tenor api key, also read from a `.env` file.
+    #[arg(long, env = "API_KEY", hide_env_values = true)]
+    api_key: String,
+
+    /// Language and optional country, for example `ja_JP`, `pt-BR` or `en`.
+    #[arg(long, short, default_value_t = Locale::default(), global = true)]
+    locale: Locale,
+
+    /// How to print the results.
+    #[arg(long, short, value_enum, default_value_t = Output::Table, global = true)]
+    output: Output,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Search for the given query.
+    Search {
+        query: String,
+        #[command(flatten)]
+        parms: SearchArgs,
+    },
+    /// Featured content of the hour.
+    Featured {
+        #[command(flatten)]
+        parms: FeaturedArgs,
+    },
+    /// Featured or trending categories.
+    Categories {
+        #[arg(value_enum)]
+        kind: CategoryKind,
+        #[arg(long, default_value = "my_test_app")]
+        client_key: String,
+        /// One of `off`, `low`, `medium` or `high`.
+        #[arg(long, default_value_t)]
+        content_filter: ContentFilter,
+    },
+    /// The hourly trending search terms.
+    TrendingTerms {
+        #[arg(long, default_value = "my_test_app")]
+        client_key: String,
+        #[arg(long, default_value_t = DEFAULT_LIMIT, value_parser = clap::value_parser!(Limit).range(1..=MAX_LIMIT as i64))]
+        limit: Limit,
+    },
+    /// Continue a search or featured listing from the `next` cursor printed with a previous page.
+    Next {
+        #[arg(value_parser = parse_cursor)]
+        cursor: Cursor,
+    },
+}
+
+#[derive(Debug, Args)]
+struct SearchArgs {
+    #[command(flatten)]
+    featured: FeaturedArgs,
+    /// Randomize the order of the results.
+    #[arg(long)]
+    random: bool,
+}
+
+#[derive(Debug, Args)]
+struct FeaturedArgs {
+    #[arg(long, default_value = "my_test_app")]
+    client_key: String,
+    /// One of `sticker`, `sticker,static` or `sticker,-static`.
+    #[arg(long)]
+    search_filter: Option<SearchFilter>,
+    /// One of `off`, `low`, `medium` or `high`.
+    #[arg(long, default_value_t)]
+    content_filter: ContentFilter,
+    /// Comma-separated list of formats, for example `gif,tinygif,mp4`.
+    #[arg(long)]
+    media_filter: Option<MediaFilters>,
+    /// One of `all`, `wide` or `standard`.
+    #[arg(long, default_value_t)]
+    ar_range: ArRange,
+    #[arg(long, default_value_t = DEFAULT_LIMIT, value_parser = clap::value_parser!(Limit).range(1..=MAX_LIMIT as i64))]
+    limit: Limit,
+    /// Start from the `next` cursor printed with a previous page.
+    #[arg(long = "pos", value_parser = parse_cursor)]
+    position: Option<Cursor>,
+    /// Overrides the country of the locale, for example `US`.
+    #[arg(long)]
+    country: Option<CountryCode>,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Output {
+    Table,
+    Json,
+    Urls,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum CategoryKind {
+    Featured,
+    Trending,
+}
+
+impl From<FeaturedArgs> for featured::Parameters {
+    fn from(value: FeaturedArgs) -> Self {
+        Self {
+            client_key: value.client_key,
+            search_filter: value.search_filter,
+            media_filter: value.media_filter,
+            ar_range: value.ar_range,
+            content_filter: value.content_filter,
+            limit: value.limit,
+            position: value.position,
+            locale: None,
+            country: value.country,
+        }
+    }
+}
+
+impl From<SearchArgs> for search::Parameters {
+    fn from(value: SearchArgs) -> Self {
+        let featured = featured::Parameters::from(value.featured);
+
+        Self {
+            client_key: featured.client_key,
+            search_filter: featured.search_filter,
+            content_filter: featured.content_filter,
+            media_filter: featured.media_filter,
+            ar_range: featured.ar_range,
+            random: value.random,
+            limit: featured.limit,
+            position: featured.position,
+            locale: featured.locale,
+            country: featured.country,
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let _ = dotenv::dotenv();
+    let cli = Cli::parse();
+    let tenor = Tenor::new(cli.api_key, cli.locale);
+
+    let printed = match cli.command {
+        Command::Search { query, parms } => tenor
+            .search_with_parameters(query, parms.into())
+            .await
+            .and_then(|response| print_results(&response, cli.output)),
+        Command::Featured { parms } => tenor
+            .featured_with_parameters(parms.into())
+            .await
+            .and_then(|response| print_results(&response, cli.output)),
+        Command::Categories {
+            kind,
+            client_key,
+            content_filter,
+        } => {
+            let parms = categories::Parameters {
+                client_key,
+                content_filter,
+                ..Default::default()
+            };
+
+            match kind {
+                CategoryKind::Featured => tenor.categories_featured_with_parameters(parms).await,
+                CategoryKind::Trending => tenor.categories_trending_with_parameters(parms).await,
+            }
+            .and_then(|response| print_categories(&response, cli.output))
+        }
+        Command::TrendingTerms { client_key, limit } => tenor
+            .trending_terms_with_parameters(trending::Parameters {
+                client_key,
+                limit,
+                ..Default::default()
+            })
+            .await
+            .and_then(|response| print_terms(&response, cli.output)),
+        Command::Next { cursor } => match (cursor.endpoint(), cursor.query()) {
+            (Endpoint::Search, Some(query)) => tenor.search_with_position(query, &cursor).await,
+            _ => tenor.featured_with_position(&cursor).await,
+        }
+        .and_then(|response| print_results(&response, cli.output)),
+    };
+
+    match printed {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Cursors are passed around as the json printed after each page.
+fn parse_cursor(value: &str) -> Result<Cursor, serde_json::Error> {
+    serde_json::from_str(value)
+}
+
+fn print_results(response: &search::Response, output: Output) -> Result<(), tenor::Error> {
+    match output {
+        Output::Table => {
+            let rows = response.results.iter().map(|object| {
+                [
+                    object.id.as_str(),
+                    object.title.as_str(),
+                    object.url.as_str(),
+                ]
+            });
+            print_table(["ID", "TITLE", "URL"], rows);
+
+            if !response.next.is_end() {
+                eprintln!("next: {}", serde_json::to_string(&response.next)?);
+            }
+        }
+        Output::Json => println!("{}", serde_json::to_string_pretty(response)?),
+        Output::Urls => response
+            .results
+            .iter()
+            .for_each(|object| println!("{}", object.url)),
+    }
+
+    Ok(())
+}
+
+fn print_categories(response: &categories::Response, output: Output) -> Result<(), tenor::Error> {
+    match output {
+        Output::Table => {
+            let rows = response.tags.iter().map(|tag| {
+                [
+                    tag.name.as_str(),
+                    tag.searchterm.as_str(),
+                    tag.image.as_str(),
+                ]
+            });
+            print_table(["NAME", "SEARCHTERM", "IMAGE"], rows);
+        }
+        Output::Json => println!("{}", serde_json::to_string_pretty(response)?),
+        Output::Urls => response
+            .tags
+            .iter()
+            .for_each(|tag| println!("{}", tag.image)),
+    }
+
+    Ok(())
+}
+
+fn print_terms(response: &trending::Response, output: Output) -> Result<(), tenor::Error> {
+    match output {
+        Output::Json => println!("{}", serde_json::to_string_pretty(response)?),
+        // Terms have no url, so both print one term per line.
+        Output::Table | Output::Urls => response.results.iter().for_each(|term| println!("{term}")),
+    }
+
+    Ok(())
+}
+
+/// Prints left aligned columns, the last column is never padded.
+fn print_table<'a, const N: usize>(header: [&'a str; N], rows: impl Iterator<Item = [&'a str; N]>) {
+    let rows: Vec<[&str; N]> = std::iter::once(header).chain(rows).collect();
+    let mut widths = [0; N];
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in &rows {
+        let mut line = String::new();
+        for (i, cell) in row.iter().enumerate() {
+            if i + 1 == N {
+                line.push_str(cell);
+            } else {
+                line.push_str(&format!("{cell:<width$}  ", width = widths[i]));
+            }
+        }
+        println!("{}", line.trim_end());
+    }
+}
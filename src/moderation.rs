@@ -0,0 +1,127 @@
+use regex::Regex;
+use serde_json::Value;
+use tracing::info;
+
+use crate::{categories::Tag, search::ResponseObject};
+
+/// Blocked words and patterns checked on top of [`ContentFilter`](crate::ContentFilter).
+///
+/// Results are checked on their `tags`, `title`, `content_description` and `flags`,
+/// categories on their name and search term, and trending terms as they are.
+/// Attach it with [`Tenor::with_blocklist`](crate::Tenor::with_blocklist) to drop matches from every response.
+#[derive(Debug, Clone, Default)]
+pub struct Blocklist {
+    rules: Vec<Regex>,
+    check_queries: bool,
+}
+
+impl Blocklist {
+    /// Creates an empty blocklist.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Blocks a word or phrase, matched as whole words in any case. Blank words are ignored.
+    ///
+    /// The word may start or end with symbols, `#nsfw` matches the text `#nsfw` but not `#nsfwx`.
+    #[must_use]
+    pub fn word(mut self, word: &str) -> Self {
+        let word = word.trim();
+        if word.is_empty() {
+            return self;
+        }
+
+        // `\b` never matches next to a symbol, so look for a non-word character or the text's edge instead.
+        let rule = format!(r"(?i)(?:^|\W){}(?:\W|$)", regex::escape(word));
+        self.rules
+            .push(Regex::new(&rule).expect("escaped words are valid patterns"));
+        self
+    }
+
+    /// Blocks every word or phrase, see [`Blocklist::word`].
+    #[must_use]
+    pub fn words<I, S>(self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        words
+            .into_iter()
+            .fold(self, |blocklist, word| blocklist.word(word.as_ref()))
+    }
+
+    /// Blocks anything matching the regular expression, use `(?i)` to ignore case.
+    pub fn pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.rules.push(Regex::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Refuses to send searches whose query is blocked, they fail with [`Error::BlockedQuery`](crate::Error::BlockedQuery).
+    #[must_use]
+    pub fn check_queries(mut self, check_queries: bool) -> Self {
+        self.check_queries = check_queries;
+        self
+    }
+
+    /// Returns the first rule matching the text.
+    pub fn matches(&self, text: &str) -> Option<&str> {
+        self.rules
+            .iter()
+            .find(|rule| rule.is_match(text))
+            .map(Regex::as_str)
+    }
+
+    /// Returns the first rule matching any of the result's checked fields.
+    pub fn matches_object(&self, object: &ResponseObject) -> Option<&str> {
+        let flags = object.flags.iter().filter_map(Value::as_str);
+
+        [object.title.as_str(), object.content_description.as_str()]
+            .into_iter()
+            .chain(object.tags.iter().map(String::as_str))
+            .chain(flags)
+            .find_map(|text| self.matches(text))
+    }
+
+    /// Returns whether the query must not be sent.
+    pub fn blocks_query(&self, query: &str) -> bool {
+        self.check_queries && self.matches(query).is_some()
+    }
+
+    /// Drops the blocked results.
+    pub fn retain_results(&self, results: &mut Vec<ResponseObject>) {
+        results.retain(|object| match self.matches_object(object) {
+            Some(rule) => {
+                info!("blocklist removed result {} matching {rule}.", object.id);
+                false
+            }
+            None => true,
+        });
+    }
+
+    /// Drops the blocked categories.
+    pub fn retain_tags(&self, tags: &mut Vec<Tag>) {
+        tags.retain(|tag| {
+            match self
+                .matches(&tag.name)
+                .or_else(|| self.matches(&tag.searchterm))
+            {
+                Some(rule) => {
+                    info!("blocklist removed category {} matching {rule}.", tag.name);
+                    false
+                }
+                None => true,
+            }
+        });
+    }
+
+    /// Drops the blocked trending terms.
+    pub fn retain_terms(&self, terms: &mut Vec<String>) {
+        terms.retain(|term| match self.matches(term) {
+            Some(rule) => {
+                info!("blocklist removed term {term} matching {rule}.");
+                false
+            }
+            None => true,
+        });
+    }
+}
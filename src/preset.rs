@@ -0,0 +1,54 @@
+//! Named parameter presets loaded from TOML, one table per preset:
+//!
+//! ```toml
+//! [reactions]
+//! content_filter = "high"
+//! media_filter = "gif,tinygif"
+//! limit = 10
+//!
+//! [stickers]
+//! search_filter = "sticker,-static"
+//! locale = "ja_JP"
+//! ```
+//!
+//! Fields left out of a preset keep their `Default` value.
+
+use std::{fs, io, path::Path};
+
+use serde::de::DeserializeOwned;
+use thiserror::Error;
+
+use crate::search;
+
+/// Error returned when a preset cannot be loaded.
+#[derive(Error, Debug)]
+pub enum PresetError {
+    #[error(transparent)]
+    Io(#[from] io::Error),
+    #[error(transparent)]
+    Toml(#[from] toml::de::Error),
+    #[error("no preset named `{0}`")]
+    Missing(String),
+}
+
+/// Reads the preset called `name` out of the given TOML.
+pub fn parse<T: DeserializeOwned>(config: &str, name: &str) -> Result<T, PresetError> {
+    let mut presets: toml::Table = config.parse()?;
+    let preset = presets
+        .remove(name)
+        .ok_or_else(|| PresetError::Missing(name.to_string()))?;
+
+    Ok(preset.try_into()?)
+}
+
+/// Reads the preset called `name` out of the TOML file at `path`.
+pub fn load<T: DeserializeOwned>(path: impl AsRef<Path>, name: &str) -> Result<T, PresetError> {
+    parse(&fs::read_to_string(path)?, name)
+}
+
+impl search::Parameters {
+    /// Loads the preset called `name` from the TOML file at `path`, see [`preset`](crate::preset) for the format.
+    pub fn from_preset(path: impl AsRef<Path>, name: &str) -> Result<Self, PresetError> {
+        load(path, name)
+    }
+}
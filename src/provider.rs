@@ -0,0 +1,230 @@
+//! Provider-neutral interface so app code doesn't have to depend on [`Tenor`] directly.
+//!
+//! A fake implementation for unit tests lives in `testing` behind the `test-util` feature.
+
+use std::{future::Future, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{Cursor, Error, Limit, MediaFilter, Tenor, categories, search, trending};
+
+/// A source of GIFs such as Tenor.
+pub trait GifProvider {
+    /// Searches for the query, continuing from `position` when given.
+    fn search(
+        &self,
+        query: &str,
+        limit: Limit,
+        position: Option<&str>,
+    ) -> impl Future<Output = Result<Page, Error>> + Send;
+
+    /// The currently featured GIFs, continuing from `position` when given.
+    fn featured(
+        &self,
+        limit: Limit,
+        position: Option<&str>,
+    ) -> impl Future<Output = Result<Page, Error>> + Send;
+
+    /// The currently trending search terms.
+    fn trending_terms(
+        &self,
+        limit: Limit,
+    ) -> impl Future<Output = Result<Vec<String>, Error>> + Send;
+
+    /// Categories to browse, each one a search query.
+    fn categories(&self) -> impl Future<Output = Result<Vec<Category>, Error>> + Send;
+
+    /// Tells the provider a GIF was shared, `query` is the search it was found with.
+    fn register_share(
+        &self,
+        id: &str,
+        query: Option<&str>,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+}
+
+/// A page of GIFs, `next` continues it and is `None` at the end.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Page {
+    pub gifs: Vec<Gif>,
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Gif {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    /// Web page of the GIF.
+    pub page_url: String,
+    pub tags: Vec<String>,
+    pub has_audio: bool,
+    /// Every file the GIF is available as, largest first within each kind.
+    pub renditions: Vec<Rendition>,
+}
+
+impl Gif {
+    /// Returns the largest rendition of the kind.
+    pub fn rendition(&self, kind: MediaKind) -> Option<&Rendition> {
+        self.renditions
+            .iter()
+            .filter(|rendition| rendition.kind == kind)
+            .max_by_key(|rendition| rendition.width)
+    }
+}
+
+/// One file of a [`Gif`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rendition {
+    pub kind: MediaKind,
+    pub url: String,
+    pub width: u32,
+    pub height: u32,
+    /// Zero for stills.
+    pub duration: Duration,
+    /// File size in bytes, zero when unknown.
+    pub size: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaKind {
+    /// A still image of the first frame.
+    Preview,
+    Gif,
+    Mp4,
+    Webm,
+    Webp,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Category {
+    pub name: String,
+    /// The search query behind the category.
+    pub query: String,
+    pub image: String,
+}
+
+impl From<search::ResponseObject> for Gif {
+    fn from(value: search::ResponseObject) -> Self {
+        Self::from(&value)
+    }
+}
+
+impl From<&search::ResponseObject> for Gif {
+    fn from(value: &search::ResponseObject) -> Self {
+        let renditions = MediaFilter::ALL
+            .iter()
+            .filter_map(|format| {
+                let media = value.media_formats.get(format)?;
+                let [width, height] = match media.dimensions {
+                    [width, height, ..] => {
+                        [*width, *height].map(|dim| u32::try_from(dim).unwrap_or(0))
+                    }
+                    _ => [0, 0],
+                };
+
+                Some(Rendition {
+                    kind: media_kind(*format)?,
+                    url: media.url.to_string(),
+                    width,
+                    height,
+                    duration: Duration::try_from_secs_f64(media.duration).unwrap_or_default(),
+                    size: u64::try_from(media.size).unwrap_or(0),
+                })
+            })
+            .collect();
+
+        Self {
+            id: value.id.clone(),
+            title: value.title.clone(),
+            description: value.content_description.clone(),
+            page_url: value.itemurl.clone(),
+            tags: value.tags.clone(),
+            has_audio: value.hasaudio,
+            renditions,
+        }
+    }
+}
+
+impl From<categories::Tag> for Category {
+    fn from(value: categories::Tag) -> Self {
+        Self {
+            name: value.name,
+            query: value.searchterm,
+            image: value.image,
+        }
+    }
+}
+
+fn media_kind(format: MediaFilter) -> Option<MediaKind> {
+    match format {
+        MediaFilter::Preview => Some(MediaKind::Preview),
+        MediaFilter::Gif | MediaFilter::MediumGif | MediaFilter::TinyGif | MediaFilter::NanoGif => {
+            Some(MediaKind::Gif)
+        }
+        MediaFilter::Mp4 | MediaFilter::LoopedMp4 | MediaFilter::TinyMp4 | MediaFilter::NanoMp4 => {
+            Some(MediaKind::Mp4)
+        }
+        MediaFilter::Webm | MediaFilter::TinyWebm | MediaFilter::NanoWebm => Some(MediaKind::Webm),
+        MediaFilter::WebpTransparent => Some(MediaKind::Webp),
+        // `ContentFormats` has no entry for these.
+        MediaFilter::TinyWebpTransparent
+        | MediaFilter::NanoWebpTransparent
+        | MediaFilter::GifTransparent
+        | MediaFilter::TinyGifTransparent
+        | MediaFilter::NanoGifTransparent => None,
+    }
+}
+
+fn page(response: search::Response) -> Page {
+    Page {
+        next: (!response.next.is_end()).then(|| response.next.position().to_string()),
+        gifs: response.results.iter().map(Gif::from).collect(),
+    }
+}
+
+impl GifProvider for Tenor {
+    async fn search(
+        &self,
+        query: &str,
+        limit: Limit,
+        position: Option<&str>,
+    ) -> Result<Page, Error> {
+        let parms = search::Parameters {
+            limit,
+            position: position.map(|position| Cursor::search(query, position.to_string())),
+            ..Default::default()
+        };
+
+        self.search_with_parameters(query, parms).await.map(page)
+    }
+
+    async fn featured(&self, limit: Limit, position: Option<&str>) -> Result<Page, Error> {
+        let parms = crate::featured::Parameters {
+            limit,
+            position: position.map(|position| Cursor::featured(position.to_string())),
+            ..Default::default()
+        };
+
+        self.featured_with_parameters(parms).await.map(page)
+    }
+
+    async fn trending_terms(&self, limit: Limit) -> Result<Vec<String>, Error> {
+        let parms = trending::Parameters {
+            limit,
+            ..Default::default()
+        };
+
+        Ok(self.trending_terms_with_parameters(parms).await?.results)
+    }
+
+    async fn categories(&self) -> Result<Vec<Category>, Error> {
+        let response = self.categories_featured().await?;
+
+        Ok(response.tags.into_iter().map(Category::from).collect())
+    }
+
+    async fn register_share(&self, id: &str, query: Option<&str>) -> Result<(), Error> {
+        Tenor::register_share(self, id, query).await
+    }
+}
@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{MediaFilter, ValidationError, search};
+
+/// Retry a result missing every preferred format up to the specified number of times.
+pub const DEFAULT_RETRIES: u32 = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Parameters {
+    /// The search to pick from, `random` is always turned on.
+    pub search: search::Parameters,
+    /// Formats in order of preference, the result must include at least one. Empty accepts any result.
+    pub formats: Vec<MediaFilter>,
+    pub retries: u32,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self {
+            search: search::Parameters::default(),
+            formats: Vec::new(),
+            retries: DEFAULT_RETRIES,
+        }
+    }
+}
+
+impl Parameters {
+    /// Returns a builder that validates the parameters when built.
+    pub fn builder() -> ParametersBuilder {
+        ParametersBuilder::default()
+    }
+
+    /// Checks the parameters against Tenor's documented constraints.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        self.search.validate()
+    }
+
+    /// Returns the first preferred format the result includes, or `None` if it has none of them.
+    /// Without a preference this is always `None`.
+    pub fn preferred_format(&self, object: &search::ResponseObject) -> Option<MediaFilter> {
+        self.formats
+            .iter()
+            .copied()
+            .find(|format| object.media_formats.get(format).is_some())
+    }
+}
+
+/// Builder for [`Parameters`], starting from the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ParametersBuilder {
+    parms: Parameters,
+}
+
+impl ParametersBuilder {
+    #[must_use]
+    pub fn search(mut self, search: search::Parameters) -> Self {
+        self.parms.search = search;
+        self
+    }
+
+    /// Adds a format after the ones already preferred.
+    #[must_use]
+    pub fn format(mut self, format: MediaFilter) -> Self {
+        if !self.parms.formats.contains(&format) {
+            self.parms.formats.push(format);
+        }
+        self
+    }
+
+    #[must_use]
+    pub fn retries(mut self, retries: u32) -> Self {
+        self.parms.retries = retries;
+        self
+    }
+
+    /// Returns the parameters if they pass [`Parameters::validate`].
+    pub fn build(self) -> Result<Parameters, ValidationError> {
+        self.parms.validate()?;
+        Ok(self.parms)
+    }
+}
+
+/// Remembers the last random result so the same one is never returned twice in a row.
+/// Keep one per caller, for example per chat channel.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Session {
+    last: Option<String>,
+}
+
+impl Session {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Id of the last result returned for this session.
+    pub fn last(&self) -> Option<&str> {
+        self.last.as_deref()
+    }
+
+    /// Picks the first result that isn't the last one returned and, with `parms` given, has a preferred format.
+    pub(crate) fn choose<'a>(
+        &self,
+        results: &'a [search::ResponseObject],
+        parms: Option<&Parameters>,
+    ) -> Option<&'a search::ResponseObject> {
+        results.iter().find(|object| {
+            self.last() != Some(object.id.as_str())
+                && parms.is_none_or(|parms| {
+                    parms.formats.is_empty() || parms.preferred_format(object).is_some()
+                })
+        })
+    }
+
+    pub(crate) fn record(&mut self, object: &search::ResponseObject) {
+        self.last = Some(object.id.clone());
+    }
+}
@@ -1,18 +1,30 @@
+use std::collections::HashSet;
+
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{ArRange, ContentFilter, DEFAULT_LIMIT, Limit, MediaFilter, SearchFilter};
+use crate::{
+    ArRange, ContentFilter, CountryCode, Cursor, DEFAULT_LIMIT, Endpoint, Error, Limit, Locale,
+    MediaFilter, MediaFilters, SearchFilter, ValidationError,
+};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Parameters {
     pub client_key: String,
     pub search_filter: Option<SearchFilter>,
     pub content_filter: ContentFilter,
-    pub media_filter: Option<&'static [MediaFilter]>,
+    /// Formats to include in each result, `None` leaves the parameter out and Tenor returns every format.
+    /// This used to default to requesting every format in [`MediaFilter::ALL`].
+    pub media_filter: Option<MediaFilters>,
     pub ar_range: ArRange,
     pub random: bool,
     pub limit: Limit,
-    pub position: Option<String>,
+    pub position: Option<Cursor>,
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    pub locale: Option<Locale>,
+    /// Overrides the country of the locale for this request.
+    pub country: Option<CountryCode>,
 }
 
 impl Default for Parameters {
@@ -21,19 +33,190 @@ impl Default for Parameters {
             client_key: "my_test_app".to_string(),
             search_filter: None,
             content_filter: ContentFilter::default(),
-            media_filter: Some(MediaFilter::DEFAULT),
+            media_filter: None,
             ar_range: ArRange::default(),
             random: false,
             limit: DEFAULT_LIMIT,
             position: None,
+            locale: None,
+            country: None,
         }
     }
 }
 
+impl Parameters {
+    /// Returns a builder that validates the parameters when built.
+    pub fn builder() -> ParametersBuilder {
+        ParametersBuilder::default()
+    }
+
+    /// Checks the parameters against Tenor's documented constraints.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        ValidationError::check_client_key(&self.client_key)?;
+        ValidationError::check_limit(self.limit)?;
+        ValidationError::check_position(self.position.as_ref(), Endpoint::Search)?;
+        ValidationError::check_media_filter(self.search_filter, self.media_filter)
+    }
+}
+
+/// Builder for [`Parameters`], starting from the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ParametersBuilder {
+    parms: Parameters,
+}
+
+impl ParametersBuilder {
+    #[must_use]
+    pub fn client_key(mut self, client_key: impl Into<String>) -> Self {
+        self.parms.client_key = client_key.into();
+        self
+    }
+
+    #[must_use]
+    pub fn search_filter(mut self, search_filter: SearchFilter) -> Self {
+        self.parms.search_filter = Some(search_filter);
+        self
+    }
+
+    #[must_use]
+    pub fn content_filter(mut self, content_filter: ContentFilter) -> Self {
+        self.parms.content_filter = content_filter;
+        self
+    }
+
+    #[must_use]
+    pub fn media_filter(mut self, media_filter: impl Into<MediaFilters>) -> Self {
+        self.parms.media_filter = Some(media_filter.into());
+        self
+    }
+
+    #[must_use]
+    pub fn ar_range(mut self, ar_range: ArRange) -> Self {
+        self.parms.ar_range = ar_range;
+        self
+    }
+
+    #[must_use]
+    pub fn random(mut self, random: bool) -> Self {
+        self.parms.random = random;
+        self
+    }
+
+    #[must_use]
+    pub fn limit(mut self, limit: Limit) -> Self {
+        self.parms.limit = limit;
+        self
+    }
+
+    /// Continue from the `next` cursor of a previous response.
+    #[must_use]
+    pub fn position(mut self, position: Cursor) -> Self {
+        self.parms.position = Some(position);
+        self
+    }
+
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    #[must_use]
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.parms.locale = Some(locale);
+        self
+    }
+
+    /// Overrides the country of the locale for this request.
+    #[must_use]
+    pub fn country(mut self, country: CountryCode) -> Self {
+        self.parms.country = Some(country);
+        self
+    }
+
+    /// Returns the parameters if they pass [`Parameters::validate`].
+    pub fn build(self) -> Result<Parameters, ValidationError> {
+        self.parms.validate()?;
+        Ok(self.parms)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Response {
     pub results: Vec<ResponseObject>,
-    pub next: String,
+    pub next: Cursor,
+}
+
+/// The response as Tenor sends it, before `next` is turned into a [`Cursor`].
+#[derive(Deserialize)]
+pub(crate) struct RawResponse {
+    pub(crate) results: Vec<ResponseObject>,
+    pub(crate) next: String,
+}
+
+/// Results of [`Tenor::search_many`](crate::Tenor::search_many), one per query in the order they were given.
+/// A failed query keeps its error without affecting the others.
+#[derive(Debug, Clone)]
+pub struct Batch {
+    entries: Vec<(String, Result<Response, Error>)>,
+}
+
+impl Batch {
+    pub(crate) fn new(entries: Vec<(String, Result<Response, Error>)>) -> Self {
+        Self { entries }
+    }
+
+    /// Returns the result of the given query.
+    pub fn get(&self, query: &str) -> Option<&Result<Response, Error>> {
+        self.entries
+            .iter()
+            .find_map(|(entry, result)| (entry == query).then_some(result))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Result<Response, Error>)> {
+        self.entries
+            .iter()
+            .map(|(query, result)| (query.as_str(), result))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Blends the successful queries into a single feed, taking one result from each query in turn.
+    /// Results that already appeared for an earlier query are skipped.
+    pub fn interleave(&self) -> Vec<ResponseObject> {
+        let mut pages: Vec<_> = self
+            .entries
+            .iter()
+            .filter_map(|(_, result)| result.as_ref().ok())
+            .map(|response| response.results.iter())
+            .collect();
+        let mut seen = HashSet::new();
+        let mut feed = Vec::new();
+
+        while !pages.is_empty() {
+            pages.retain_mut(|page| match page.next() {
+                Some(object) => {
+                    if seen.insert(&object.id) {
+                        feed.push(object.clone());
+                    }
+                    true
+                }
+                None => false,
+            });
+        }
+
+        feed
+    }
+}
+
+impl IntoIterator for Batch {
+    type Item = (String, Result<Response, Error>);
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.into_iter()
+    }
 }
 
 /// Note: Even though `hascaption` and `bg_color` are listed in the table they do not exist in the
@@ -82,6 +265,56 @@ pub struct ContentFormats {
     pub nanogifpreview: Option<Nanogifpreview>,
 }
 
+impl ContentFormats {
+    /// Returns the entry matching the given [`MediaFilter`], if Tenor included it.
+    ///
+    /// Only `webp_transparent` of the transparent filters has an entry, the rest always return `None`.
+    pub fn get(&self, filter: &MediaFilter) -> Option<Media<'_>> {
+        macro_rules! media {
+            ($field:ident) => {
+                self.$field.as_ref().map(|format| Media {
+                    url: &format.url,
+                    duration: format.duration,
+                    preview: &format.preview,
+                    dimensions: &format.dimensions,
+                    size: format.size,
+                })
+            };
+        }
+
+        match filter {
+            MediaFilter::Preview => media!(gifpreview),
+            MediaFilter::Gif => media!(gif),
+            MediaFilter::MediumGif => media!(mediumgif),
+            MediaFilter::TinyGif => media!(tinygif),
+            MediaFilter::NanoGif => media!(nanogif),
+            MediaFilter::Mp4 => media!(mp4),
+            MediaFilter::LoopedMp4 => media!(loopedmp4),
+            MediaFilter::TinyMp4 => media!(tinymp4),
+            MediaFilter::NanoMp4 => media!(nanomp4),
+            MediaFilter::Webm => media!(webm),
+            MediaFilter::TinyWebm => media!(tinywebm),
+            MediaFilter::NanoWebm => media!(nanowebm),
+            MediaFilter::WebpTransparent => media!(webp),
+            MediaFilter::TinyWebpTransparent
+            | MediaFilter::NanoWebpTransparent
+            | MediaFilter::GifTransparent
+            | MediaFilter::TinyGifTransparent
+            | MediaFilter::NanoGifTransparent => None,
+        }
+    }
+}
+
+/// Borrowed view of a single [`ContentFormats`] entry, every format shares the same fields.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Media<'a> {
+    pub url: &'a str,
+    pub duration: f64,
+    pub preview: &'a str,
+    pub dimensions: &'a [i64],
+    pub size: i64,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Gifpreview {
     pub url: String,
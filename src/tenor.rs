@@ -1,46 +1,133 @@
-use reqwest;
-use tracing::{debug, error};
+use futures::{StreamExt, stream};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use tracing::{debug, error, info};
 
-use crate::{Error, Locale, categories, featured, search, trending};
+use std::{borrow::Cow, collections::HashSet, sync::Arc, time::Instant};
+
+use crate::{
+    CountryCode, Cursor, Endpoint, Error, Limit, Locale, MAX_LIMIT, MediaFilter, MediaFilters,
+    RequestContext, ResponseMeta, Tracked, ValidationError, categories,
+    download::Downloader,
+    featured,
+    filter::ResultFilter,
+    moderation::Blocklist,
+    random, search,
+    transport::{self, TenorRequest, TenorResponse, Transport},
+    trending,
+};
+
+const BASE_URL: &str = "https://tenor.googleapis.com/v2";
+
+/// How much of a failed response's body is kept in the error.
+const BODY_PREFIX_LEN: usize = 512;
 
 /// Immutable type holding the api key along with region and language codes.
 #[derive(Debug, Clone)]
 pub struct Tenor {
-    api_key: String,
+    api_key: Arc<str>,
     locale: Locale,
+    base_url: Arc<str>,
+    client: reqwest::Client,
+    transport: Transport,
+    blocklist: Option<Arc<Blocklist>>,
 }
 
 impl Tenor {
     /// Creates a new instance.
     #[must_use]
-    pub fn new(api_key: String, locale: Locale) -> Self {
-        Self { api_key, locale }
+    pub fn new(api_key: impl AsRef<str>, locale: Locale) -> Self {
+        Self::with_client(api_key, locale, reqwest::Client::new())
+    }
+
+    /// Creates a new instance that sends every request through the given client,
+    /// use this to configure timeouts, proxies or other connection settings.
+    #[must_use]
+    pub fn with_client(api_key: impl AsRef<str>, locale: Locale, client: reqwest::Client) -> Self {
+        Self {
+            api_key: Arc::from(api_key.as_ref()),
+            locale,
+            base_url: Arc::from(BASE_URL),
+            transport: Transport::http(client.clone()),
+            client,
+            blocklist: None,
+        }
+    }
+
+    /// Creates a new instance that sends api requests through `service` instead of its own client,
+    /// for example [`HttpService`](crate::transport::HttpService) wrapped in timeout or concurrency limit layers.
+    /// Downloads still use a default client.
+    #[cfg(feature = "tower")]
+    #[must_use]
+    pub fn with_service<S>(api_key: impl AsRef<str>, locale: Locale, service: S) -> Self
+    where
+        S: tower::Service<TenorRequest, Response = TenorResponse> + Clone + Send + 'static,
+        S::Error: Into<crate::transport::BoxError>,
+        S::Future: Send,
+    {
+        Self::with_transport(api_key, locale, Transport::service(service))
+    }
+
+    #[cfg(any(feature = "tower", feature = "test-util"))]
+    pub(crate) fn with_transport(
+        api_key: impl AsRef<str>,
+        locale: Locale,
+        transport: Transport,
+    ) -> Self {
+        Self {
+            transport,
+            ..Self::new(api_key, locale)
+        }
+    }
+
+    /// Returns a copy using a different locale, the api key and client are shared with this instance.
+    #[must_use]
+    pub fn with_locale(&self, locale: Locale) -> Tenor {
+        Self {
+            locale,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy that sends api requests to `base_url` instead of `https://tenor.googleapis.com/v2`,
+    /// for example a proxy or `testing::MockTenor`.
+    #[must_use]
+    pub fn with_base_url(&self, base_url: impl AsRef<str>) -> Tenor {
+        Self {
+            base_url: Arc::from(base_url.as_ref().trim_end_matches('/')),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a copy that drops blocked results, categories and trending terms from every response.
+    #[must_use]
+    pub fn with_blocklist(&self, blocklist: Blocklist) -> Tenor {
+        Self {
+            blocklist: Some(Arc::new(blocklist)),
+            ..self.clone()
+        }
+    }
+
+    /// Returns a [`Downloader`] for the given format that shares this instance's client.
+    #[must_use]
+    pub fn downloader(&self, format: MediaFilter) -> Downloader {
+        Downloader::new(self.client.clone(), format)
     }
 
     /// Returns a vector of tagged categories that are featured.
     #[must_use]
     pub async fn categories_featured(&self) -> Result<categories::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/categories?key={}{}{}",
-            self.api_key,
-            self.locale.to_query_parameter(),
-            "&type=featured",
-        );
+        let url = self.categories_url(categories::Type::Featured, None);
 
-        self.categories_request(url).await
+        self.categories_request(url).await.map(Tracked::into_inner)
     }
 
     /// Returns a vector of tagged categories that are trending.
     #[must_use]
     pub async fn categories_trending(&self) -> Result<categories::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/categories?key={}{}{}",
-            self.api_key,
-            self.locale.to_query_parameter(),
-            "&type=trending",
-        );
+        let url = self.categories_url(categories::Type::Trending, None);
 
-        self.categories_request(url).await
+        self.categories_request(url).await.map(Tracked::into_inner)
     }
 
     /// Returns a vector of tagged categories that are featured with extra input parameters.
@@ -49,17 +136,9 @@ impl Tenor {
         &self,
         parms: categories::Parameters,
     ) -> Result<categories::Response, Error> {
-        let mut url = format!(
-            "https://tenor.googleapis.com/v2/categories?key={}{}{}",
-            self.api_key,
-            self.locale.to_query_parameter(),
-            "&type=featured",
-        );
-
-        url.push_str(&format!("&client_key={}", &parms.client_key));
-        url.push_str(&parms.content_filter.to_query_parameter());
+        let url = self.categories_url(categories::Type::Featured, Some(&parms));
 
-        self.categories_request(url).await
+        self.categories_request(url).await.map(Tracked::into_inner)
     }
 
     /// Returns a vector of tagged categories that are trending with extra input parameters.
@@ -68,56 +147,135 @@ impl Tenor {
         &self,
         parms: categories::Parameters,
     ) -> Result<categories::Response, Error> {
+        let url = self.categories_url(categories::Type::Trending, Some(&parms));
+
+        self.categories_request(url).await.map(Tracked::into_inner)
+    }
+
+    /// Like [`Tenor::categories_featured_with_parameters`] and [`Tenor::categories_trending_with_parameters`],
+    /// but also returns the status, headers and timing of the response.
+    #[must_use]
+    pub async fn categories_with_meta(
+        &self,
+        kind: categories::Type,
+        parms: categories::Parameters,
+    ) -> Result<Tracked<categories::Response>, Error> {
+        let url = self.categories_url(kind, Some(&parms));
+
+        self.categories_request(url).await
+    }
+
+    /// Like [`Tenor::categories_with_meta`], but returns the json as Tenor sent it without the blocklist applied.
+    #[must_use]
+    pub async fn categories_raw(
+        &self,
+        kind: categories::Type,
+        parms: categories::Parameters,
+    ) -> Result<Value, Error> {
+        let url = self.categories_url(kind, Some(&parms));
+
+        self.request("categories", url)
+            .await
+            .map(Tracked::into_inner)
+    }
+
+    /// Returns the first page of results for a category's search term.
+    #[must_use]
+    pub async fn category_results(
+        &self,
+        tag: &categories::Tag,
+        parms: search::Parameters,
+    ) -> Result<search::Response, Error> {
+        self.search_with_parameters(&tag.searchterm, parms).await
+    }
+
+    /// Returns every featured or trending category along with the first page of its results,
+    /// running up to `concurrency` searches at once. A failed search only affects its own category.
+    #[must_use]
+    pub async fn categories_with_previews(
+        &self,
+        kind: categories::Type,
+        category_parms: categories::Parameters,
+        parms: search::Parameters,
+        concurrency: usize,
+    ) -> Result<Vec<categories::Preview>, Error> {
+        let url = self.categories_url(kind, Some(&category_parms));
+        let response = self.categories_request(url).await?.into_inner();
+
+        let previews = stream::iter(response.tags)
+            .map(|tag| {
+                let parms = parms.clone();
+                async move {
+                    let results = self.category_results(&tag, parms).await;
+                    categories::Preview { tag, results }
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        Ok(previews)
+    }
+
+    pub(crate) fn categories_url(
+        &self,
+        kind: categories::Type,
+        parms: Option<&categories::Parameters>,
+    ) -> String {
+        let Some(parms) = parms else {
+            return format!(
+                "{}/categories?key={}{}&type={kind}",
+                self.base_url,
+                self.api_key,
+                self.locale.to_query_parameter(None),
+            );
+        };
+
         let mut url = format!(
-            "https://tenor.googleapis.com/v2/categories?key={}{}{}",
+            "{}/categories?key={}{}&type={kind}",
+            self.base_url,
             self.api_key,
-            self.locale.to_query_parameter(),
-            "&type=trending",
+            self.locale_query_parameter(parms.locale, parms.country),
         );
 
-        url.push_str(&format!("&client_key={}", &parms.client_key));
+        url.push_str(&format!("&client_key={}", encode(&parms.client_key)));
         url.push_str(&parms.content_filter.to_query_parameter());
 
-        self.categories_request(url).await
+        url
     }
 
-    async fn categories_request(&self, url: String) -> Result<categories::Response, Error> {
-        let response = reqwest::get(url).await;
-
-        match response.is_ok() {
-            true => debug!("fetch categories successful."),
-            false => error!("fetch categories failed."),
-        }
+    async fn categories_request(
+        &self,
+        url: String,
+    ) -> Result<Tracked<categories::Response>, Error> {
+        let tracked = self.request("categories", url).await?;
 
-        let response_body = response?.text().await?;
-        let obj: categories::Response = serde_json::from_str(&response_body)?;
-        Ok(obj)
+        Ok(tracked.map(|mut obj: categories::Response| {
+            if let Some(blocklist) = &self.blocklist {
+                blocklist.retain_tags(&mut obj.tags);
+            }
+            obj
+        }))
     }
 
     /// Returns the featured stickers of the hour, have recalled every hour and cache contents.
     #[must_use]
     pub async fn featured(&self) -> Result<search::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/featured?key={}{}",
-            self.api_key,
-            self.locale.to_query_parameter(),
-        );
-        self.featured_request(url).await
+        let url = self.featured_url(None);
+
+        self.featured_request(url).await.map(Tracked::into_inner)
     }
 
-    // Continue from where feature left off, use the `next` field as the input for `position`.
+    // Continue from where feature left off, use the `next` cursor as the input for `position`.
     #[must_use]
     pub async fn featured_with_position(
         &self,
-        position: String,
+        position: &Cursor,
     ) -> Result<search::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/featured?key={}{}&pos={}",
-            self.api_key,
-            self.locale.to_query_parameter(),
-            position,
-        );
-        self.featured_request(url).await
+        let position = position.position_for(Endpoint::Featured, None)?;
+        let url = format!("{}&pos={}", self.featured_url(None), encode(position));
+
+        self.featured_request(url).await.map(Tracked::into_inner)
     }
 
     /// Return the featured stickers with extra parameters of the hour, have recalled every hour and cache contents.
@@ -126,10 +284,100 @@ impl Tenor {
         &self,
         parms: featured::Parameters,
     ) -> Result<search::Response, Error> {
+        self.featured_with_meta(parms)
+            .await
+            .map(Tracked::into_inner)
+    }
+
+    /// Like [`Tenor::featured_with_parameters`], but also returns the status, headers and timing of the response.
+    #[must_use]
+    pub async fn featured_with_meta(
+        &self,
+        parms: featured::Parameters,
+    ) -> Result<Tracked<search::Response>, Error> {
+        if let Some(cursor) = &parms.position {
+            cursor.position_for(Endpoint::Featured, None)?;
+        }
+        let url = self.featured_url(Some(&parms));
+
+        self.featured_request(url).await
+    }
+
+    /// Like [`Tenor::featured_with_parameters`], but returns the json as Tenor sent it without the blocklist applied.
+    #[must_use]
+    pub async fn featured_raw(&self, parms: featured::Parameters) -> Result<Value, Error> {
+        if let Some(cursor) = &parms.position {
+            cursor.position_for(Endpoint::Featured, None)?;
+        }
+        let url = self.featured_url(Some(&parms));
+
+        self.request("featured", url).await.map(Tracked::into_inner)
+    }
+
+    /// Returns the featured response body unparsed, read it with [`borrowed::Response::parse`](crate::borrowed::Response::parse) to avoid copying its strings.
+    /// The blocklist isn't applied.
+    #[must_use]
+    pub async fn featured_body(
+        &self,
+        parms: featured::Parameters,
+    ) -> Result<Tracked<String>, Error> {
+        if let Some(cursor) = &parms.position {
+            cursor.position_for(Endpoint::Featured, None)?;
+        }
+        let url = self.featured_url(Some(&parms));
+
+        self.fetch("featured", url).await
+    }
+
+    /// Returns up to `n` featured results, chaining as many requests as needed since each one is capped at [`MAX_LIMIT`].
+    /// Results are deduplicated by id and `next` continues after the last request.
+    #[must_use]
+    pub async fn featured_n(
+        &self,
+        n: usize,
+        parms: featured::Parameters,
+    ) -> Result<search::Response, Error> {
+        self.featured_n_filtered(n, parms, &ResultFilter::default())
+            .await
+    }
+
+    /// Like [`Tenor::featured_n`], but only keeps results passing `filter`, fetching more pages to make up for the dropped ones.
+    /// Each request asks for a full page of [`MAX_LIMIT`] results and any beyond `n` are dropped.
+    #[must_use]
+    pub async fn featured_n_filtered(
+        &self,
+        n: usize,
+        parms: featured::Parameters,
+        filter: &ResultFilter,
+    ) -> Result<search::Response, Error> {
+        let position = parms.position.clone();
+        let start = Cursor::featured(String::new());
+        self.paginate(n, position, start, filter, |position, limit| {
+            self.featured_with_parameters(featured::Parameters {
+                limit,
+                position,
+                ..parms.clone()
+            })
+        })
+        .await
+    }
+
+    pub(crate) fn featured_url(&self, parms: Option<&featured::Parameters>) -> String {
+        let Some(parms) = parms else {
+            return format!(
+                "{}/featured?key={}{}",
+                self.base_url,
+                self.api_key,
+                self.locale.to_query_parameter(None),
+            );
+        };
+
         let mut url = format!(
-            "https://tenor.googleapis.com/v2/featured?key={}{}",
+            "{}/featured?key={}{}&client_key={}",
+            self.base_url,
             self.api_key,
-            self.locale.to_query_parameter(),
+            self.locale_query_parameter(parms.locale, parms.country),
+            encode(&parms.client_key),
         );
 
         if let Some(filter) = parms.search_filter {
@@ -137,82 +385,265 @@ impl Tenor {
         }
 
         if let Some(filters) = parms.media_filter {
-            let mut base = String::from("&media_filter=");
-
-            for media_type in filters {
-                base.push_str(&media_type.to_string());
-                base.push(',');
-            }
-            url.push_str(&base);
+            url.push_str(&filters.to_query_parameter());
         }
 
         url.push_str(&parms.ar_range.to_query_parameter());
         url.push_str(&parms.content_filter.to_query_parameter());
         url.push_str(&format!("&limit={}", parms.limit));
-        if let Some(pos) = parms.position {
-            url.push_str(&format!("&pos={}", pos));
+        if let Some(cursor) = &parms.position {
+            url.push_str(&format!("&pos={}", encode(cursor.position())));
         }
 
-        self.featured_request(url).await
+        url
     }
 
-    async fn featured_request(&self, url: String) -> Result<search::Response, Error> {
-        let response = reqwest::get(url).await;
+    async fn featured_request(&self, url: String) -> Result<Tracked<search::Response>, Error> {
+        let tracked = self.request("featured", url).await?;
 
-        match response.is_ok() {
-            true => debug!("fetch featured successful."),
-            false => error!("fetch featured failed."),
-        }
-
-        let response_body = response?.text().await?;
-        let obj: search::Response = serde_json::from_str(&response_body)?;
-        Ok(obj)
+        Ok(tracked.map(|mut obj: search::RawResponse| {
+            if let Some(blocklist) = &self.blocklist {
+                blocklist.retain_results(&mut obj.results);
+            }
+            search::Response {
+                results: obj.results,
+                next: Cursor::featured(obj.next),
+            }
+        }))
     }
 
     /// Searchs Tenor with the given query.
     #[must_use]
-    pub async fn search(&self, query: String) -> Result<search::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/search?q={}&key={}{}",
-            query,
-            self.api_key,
-            self.locale.to_query_parameter(),
-        );
+    pub async fn search(&self, query: impl AsRef<str>) -> Result<search::Response, Error> {
+        let query = query.as_ref();
+        let url = self.search_url(query, None);
 
-        self.search_request(url).await
+        self.search_request(url, query)
+            .await
+            .map(Tracked::into_inner)
     }
 
     /// Continue searching Tenor with the given query, starting from where the last request ended.
+    /// The cursor must come from a search for the same query.
     #[must_use]
     pub async fn search_with_position(
         &self,
-        query: String,
-        position: String,
+        query: impl AsRef<str>,
+        position: &Cursor,
     ) -> Result<search::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/search?q={}&key={}{}&pos={}",
-            query,
-            self.api_key,
-            self.locale.to_query_parameter(),
-            position,
-        );
+        let query = query.as_ref();
+        let position = position.position_for(Endpoint::Search, Some(query))?;
+        let url = format!("{}&pos={}", self.search_url(query, None), encode(position));
 
-        self.search_request(url).await
+        self.search_request(url, query)
+            .await
+            .map(Tracked::into_inner)
     }
 
     /// Searchs Tenor with the given query with extra parameters.
     #[must_use]
     pub async fn search_with_parameters(
         &self,
-        query: String,
+        query: impl AsRef<str>,
         parms: search::Parameters,
     ) -> Result<search::Response, Error> {
-        let mut url = format!(
-            "https://tenor.googleapis.com/v2/search?q={}&key={}{}&client_key={}",
+        self.search_with_meta(query, parms)
+            .await
+            .map(Tracked::into_inner)
+    }
+
+    /// Like [`Tenor::search_with_parameters`], but also returns the status, headers and timing of the response.
+    #[must_use]
+    pub async fn search_with_meta(
+        &self,
+        query: impl AsRef<str>,
+        parms: search::Parameters,
+    ) -> Result<Tracked<search::Response>, Error> {
+        let query = query.as_ref();
+        if let Some(cursor) = &parms.position {
+            cursor.position_for(Endpoint::Search, Some(query))?;
+        }
+        let url = self.search_url(query, Some(&parms));
+
+        self.search_request(url, query).await
+    }
+
+    /// Like [`Tenor::search_with_parameters`], but returns the json as Tenor sent it without the blocklist applied to the results.
+    #[must_use]
+    pub async fn search_raw(
+        &self,
+        query: impl AsRef<str>,
+        parms: search::Parameters,
+    ) -> Result<Value, Error> {
+        let query = query.as_ref();
+        if let Some(cursor) = &parms.position {
+            cursor.position_for(Endpoint::Search, Some(query))?;
+        }
+        self.check_query(query)?;
+        let url = self.search_url(query, Some(&parms));
+
+        self.request("search", url).await.map(Tracked::into_inner)
+    }
+
+    /// Returns the search response body unparsed, read it with [`borrowed::Response::parse`](crate::borrowed::Response::parse) to avoid copying its strings.
+    /// The blocklist is only applied to the query.
+    #[must_use]
+    pub async fn search_body(
+        &self,
+        query: impl AsRef<str>,
+        parms: search::Parameters,
+    ) -> Result<Tracked<String>, Error> {
+        let query = query.as_ref();
+        if let Some(cursor) = &parms.position {
+            cursor.position_for(Endpoint::Search, Some(query))?;
+        }
+        self.check_query(query)?;
+        let url = self.search_url(query, Some(&parms));
+
+        self.fetch("search", url).await
+    }
+
+    /// Searches Tenor for up to `n` results, chaining as many requests as needed since each one is capped at [`MAX_LIMIT`].
+    /// Results are deduplicated by id and `next` continues after the last request.
+    #[must_use]
+    pub async fn search_n(
+        &self,
+        query: impl AsRef<str>,
+        n: usize,
+        parms: search::Parameters,
+    ) -> Result<search::Response, Error> {
+        self.search_n_filtered(query, n, parms, &ResultFilter::default())
+            .await
+    }
+
+    /// Like [`Tenor::search_n`], but only keeps results passing `filter`, fetching more pages to make up for the dropped ones.
+    /// Each request asks for a full page of [`MAX_LIMIT`] results and any beyond `n` are dropped.
+    #[must_use]
+    pub async fn search_n_filtered(
+        &self,
+        query: impl AsRef<str>,
+        n: usize,
+        parms: search::Parameters,
+        filter: &ResultFilter,
+    ) -> Result<search::Response, Error> {
+        let query = query.as_ref();
+        let position = parms.position.clone();
+        let start = Cursor::search(query, String::new());
+        self.paginate(n, position, start, filter, |position, limit| {
+            self.search_with_parameters(
+                query,
+                search::Parameters {
+                    limit,
+                    position,
+                    ..parms.clone()
+                },
+            )
+        })
+        .await
+    }
+
+    /// Returns one random result for the query, or `None` if there are none.
+    #[must_use]
+    pub async fn random(
+        &self,
+        query: impl AsRef<str>,
+    ) -> Result<Option<search::ResponseObject>, Error> {
+        self.random_with_parameters(
             query,
+            random::Parameters::default(),
+            &mut random::Session::new(),
+        )
+        .await
+    }
+
+    /// Returns one random result for the query that isn't the session's last one, preferring results in the given formats.
+    /// A page without a preferred format is retried up to [`random::Parameters::retries`] times,
+    /// after that any result is returned. `None` means there was nothing new to return.
+    #[must_use]
+    pub async fn random_with_parameters(
+        &self,
+        query: impl AsRef<str>,
+        parms: random::Parameters,
+        session: &mut random::Session,
+    ) -> Result<Option<search::ResponseObject>, Error> {
+        let query = query.as_ref();
+        let search_parms = search::Parameters {
+            random: true,
+            ..parms.search.clone()
+        };
+        let mut fallback = None;
+
+        for attempt in 0..=parms.retries {
+            let results = self
+                .search_with_parameters(query, search_parms.clone())
+                .await?
+                .results;
+            if results.is_empty() {
+                break;
+            }
+
+            if let Some(object) = session.choose(&results, Some(&parms)) {
+                session.record(object);
+                return Ok(Some(object.clone()));
+            }
+            debug!("random attempt {attempt} found no {query} result in a preferred format.");
+            fallback = fallback.or_else(|| session.choose(&results, None).cloned());
+        }
+
+        if let Some(object) = &fallback {
+            session.record(object);
+        }
+        Ok(fallback)
+    }
+
+    /// Searches Tenor for every query, running up to `concurrency` requests at once.
+    /// Each query gets its own result so one failing doesn't lose the others,
+    /// use [`search::Batch::interleave`] to blend them into one feed.
+    #[must_use]
+    pub async fn search_many<I, S>(
+        &self,
+        queries: I,
+        parms: search::Parameters,
+        concurrency: usize,
+    ) -> search::Batch
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let entries = stream::iter(queries)
+            .map(|query| {
+                let query = query.into();
+                let parms = parms.clone();
+                async move {
+                    let result = self.search_with_parameters(&query, parms).await;
+                    (query, result)
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        search::Batch::new(entries)
+    }
+
+    pub(crate) fn search_url(&self, query: &str, parms: Option<&search::Parameters>) -> String {
+        let Some(parms) = parms else {
+            return format!(
+                "{}/search?q={}&key={}{}",
+                self.base_url,
+                encode(query),
+                self.api_key,
+                self.locale.to_query_parameter(None),
+            );
+        };
+
+        let mut url = format!(
+            "{}/search?q={}&key={}{}&client_key={}",
+            self.base_url,
+            encode(query),
             self.api_key,
-            self.locale.to_query_parameter(),
-            parms.client_key,
+            self.locale_query_parameter(parms.locale, parms.country),
+            encode(&parms.client_key),
         );
 
         if let Some(filter) = parms.search_filter {
@@ -222,49 +653,44 @@ impl Tenor {
         url.push_str(&parms.content_filter.to_query_parameter());
 
         if let Some(filters) = parms.media_filter {
-            let mut base = String::from("&media_filter=");
-
-            for media_type in filters {
-                base.push_str(&media_type.to_string());
-                base.push(',');
-            }
-            url.push_str(&base);
+            url.push_str(&filters.to_query_parameter());
         }
 
         url.push_str(&parms.ar_range.to_query_parameter());
         url.push_str(&format!("&random={}", parms.random));
         url.push_str(&format!("&limit={}", parms.limit));
-        if let Some(pos) = parms.position {
-            url.push_str(&format!("&pos={}", pos));
+        if let Some(cursor) = &parms.position {
+            url.push_str(&format!("&pos={}", encode(cursor.position())));
         }
 
-        self.search_request(url).await
+        url
     }
 
-    async fn search_request(&self, url: String) -> Result<search::Response, Error> {
-        let response = reqwest::get(&url).await;
-
-        match response.is_ok() {
-            true => debug!("search query successful.",),
-            false => error!("search query failed {:#?}.", &response),
-        }
-
-        let response_body = response?.text().await?;
-        let obj: search::Response = serde_json::from_str(&response_body)?;
+    async fn search_request(
+        &self,
+        url: String,
+        query: &str,
+    ) -> Result<Tracked<search::Response>, Error> {
+        self.check_query(query)?;
+        let tracked = self.request("search", url).await?;
 
-        Ok(obj)
+        Ok(tracked.map(|mut obj: search::RawResponse| {
+            if let Some(blocklist) = &self.blocklist {
+                blocklist.retain_results(&mut obj.results);
+            }
+            search::Response {
+                results: obj.results,
+                next: Cursor::search(query, obj.next),
+            }
+        }))
     }
 
     /// Returns the hourly tending search terms.
     #[must_use]
     pub async fn trending_terms(&self) -> Result<trending::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/trending_terms?key={}{}",
-            self.api_key,
-            self.locale.to_query_parameter(),
-        );
+        let url = self.trending_terms_url(None);
 
-        self.trending_request(url).await
+        self.trending_request(url).await.map(Tracked::into_inner)
     }
 
     /// Returns the hourly tending search terms with extra parameters.
@@ -273,27 +699,343 @@ impl Tenor {
         &self,
         parms: trending::Parameters,
     ) -> Result<trending::Response, Error> {
-        let url = format!(
-            "https://tenor.googleapis.com/v2/trending_terms?key={}{}&client_key={}&limit={}",
+        self.trending_terms_with_meta(parms)
+            .await
+            .map(Tracked::into_inner)
+    }
+
+    /// Like [`Tenor::trending_terms_with_parameters`], but also returns the status, headers and timing of the response.
+    #[must_use]
+    pub async fn trending_terms_with_meta(
+        &self,
+        parms: trending::Parameters,
+    ) -> Result<Tracked<trending::Response>, Error> {
+        let url = self.trending_terms_url(Some(&parms));
+
+        self.trending_request(url).await
+    }
+
+    /// Like [`Tenor::trending_terms_with_parameters`], but returns the json as Tenor sent it without the blocklist applied.
+    #[must_use]
+    pub async fn trending_terms_raw(&self, parms: trending::Parameters) -> Result<Value, Error> {
+        let url = self.trending_terms_url(Some(&parms));
+
+        self.request("trending_terms", url)
+            .await
+            .map(Tracked::into_inner)
+    }
+
+    /// Tells Tenor a result was shared, which improves its future search results. `query` is the search the result came from.
+    #[must_use]
+    pub async fn register_share(&self, id: &str, query: Option<&str>) -> Result<(), Error> {
+        let url = self.register_share_url(id, query);
+
+        self.request::<Value>("registershare", url)
+            .await
+            .map(|_| ())
+    }
+
+    pub(crate) fn register_share_url(&self, id: &str, query: Option<&str>) -> String {
+        let mut url = format!(
+            "{}/registershare?id={}&key={}{}",
+            self.base_url,
+            encode(id),
             self.api_key,
-            self.locale.to_query_parameter(),
-            parms.client_key,
-            parms.limit,
+            self.locale.to_query_parameter(None),
         );
+        if let Some(query) = query {
+            url.push_str(&format!("&q={}", encode(query)));
+        }
 
-        self.trending_request(url).await
+        url
     }
 
-    async fn trending_request(&self, url: String) -> Result<trending::Response, Error> {
-        let response = reqwest::get(url).await;
+    /// Sends `parameters` to any endpoint, for example `autocomplete`, and returns the json as Tenor sent it.
+    /// The api key and this instance's locale are added like they are for the typed methods.
+    #[must_use]
+    pub async fn get_json(
+        &self,
+        endpoint: &str,
+        parameters: &[(&str, &str)],
+    ) -> Result<Value, Error> {
+        let url = self.json_url(endpoint, parameters);
+
+        self.request(endpoint.to_string(), url)
+            .await
+            .map(Tracked::into_inner)
+    }
 
-        match response.is_ok() {
-            true => debug!("fetch trending successful."),
-            false => error!("fetch trending failed."),
+    pub(crate) fn json_url(&self, endpoint: &str, parameters: &[(&str, &str)]) -> String {
+        let mut url = format!(
+            "{}/{endpoint}?key={}{}",
+            self.base_url,
+            self.api_key,
+            self.locale.to_query_parameter(None),
+        );
+        for (name, value) in parameters {
+            url.push_str(&format!("&{}={}", encode(name), encode(value)));
         }
 
-        let response_body = response?.text().await?;
-        let obj: trending::Response = serde_json::from_str(&response_body)?;
-        Ok(obj)
+        url
+    }
+
+    /// Returns the hourly trending search terms, each with up to `per_term_limit` results in the given formats.
+    /// The searches share the locale and client key of `parms`, up to `concurrency` run at once
+    /// and a failed search only affects its own term.
+    #[must_use]
+    pub async fn trending_terms_with_previews(
+        &self,
+        parms: trending::Parameters,
+        per_term_limit: Limit,
+        media_filter: Option<MediaFilters>,
+        concurrency: usize,
+    ) -> Result<Vec<trending::Preview>, Error> {
+        ValidationError::check_limit(per_term_limit)?;
+        let url = self.trending_terms_url(Some(&parms));
+        let terms = self.trending_request(url).await?.value.results;
+
+        let search_parms = search::Parameters {
+            client_key: parms.client_key,
+            media_filter,
+            limit: per_term_limit,
+            locale: parms.locale,
+            country: parms.country,
+            ..Default::default()
+        };
+
+        let previews = stream::iter(terms)
+            .map(|term| {
+                let parms = search_parms.clone();
+                async move {
+                    let results = self.search_with_parameters(&term, parms).await;
+                    trending::Preview { term, results }
+                }
+            })
+            .buffered(concurrency.max(1))
+            .collect()
+            .await;
+
+        Ok(previews)
+    }
+
+    pub(crate) fn trending_terms_url(&self, parms: Option<&trending::Parameters>) -> String {
+        let Some(parms) = parms else {
+            return format!(
+                "{}/trending_terms?key={}{}",
+                self.base_url,
+                self.api_key,
+                self.locale.to_query_parameter(None),
+            );
+        };
+
+        format!(
+            "{}/trending_terms?key={}{}&client_key={}&limit={}",
+            self.base_url,
+            self.api_key,
+            self.locale_query_parameter(parms.locale, parms.country),
+            encode(&parms.client_key),
+            parms.limit,
+        )
+    }
+
+    async fn trending_request(&self, url: String) -> Result<Tracked<trending::Response>, Error> {
+        let tracked = self.request("trending_terms", url).await?;
+
+        Ok(tracked.map(|mut obj: trending::Response| {
+            if let Some(blocklist) = &self.blocklist {
+                blocklist.retain_terms(&mut obj.results);
+            }
+            obj
+        }))
+    }
+
+    /// Refuses queries blocked by the blocklist, when it checks queries.
+    fn check_query(&self, query: &str) -> Result<(), Error> {
+        match &self.blocklist {
+            Some(blocklist) if blocklist.blocks_query(query) => {
+                info!("blocklist refused to search {query}.");
+                Err(Error::BlockedQuery(query.to_string()))
+            }
+            _ => Ok(()),
+        }
     }
+
+    /// Sends the request and decodes the json body, failures carry the endpoint, redacted url and status.
+    async fn request<T: DeserializeOwned>(
+        &self,
+        endpoint: impl Into<Cow<'static, str>>,
+        url: String,
+    ) -> Result<Tracked<T>, Error> {
+        let endpoint = endpoint.into();
+        let body = self.fetch(endpoint.clone(), url).await?;
+
+        match serde_json::from_str(&body.value) {
+            Ok(value) => Ok(Tracked {
+                value,
+                meta: body.meta,
+            }),
+            Err(source) => Err(Error::Decode {
+                context: RequestContext {
+                    endpoint,
+                    url: body.meta.url,
+                    status: Some(body.meta.status),
+                },
+                body: body_prefix(&body.value),
+                source: Arc::new(source),
+            }),
+        }
+    }
+
+    /// Sends the request and returns the body of a successful response.
+    async fn fetch(
+        &self,
+        endpoint: impl Into<Cow<'static, str>>,
+        url: String,
+    ) -> Result<Tracked<String>, Error> {
+        let endpoint = endpoint.into();
+        let mut context = RequestContext {
+            endpoint: endpoint.clone(),
+            url: redact_url(&url),
+            status: None,
+        };
+
+        let start = Instant::now();
+        let TenorResponse {
+            status,
+            headers,
+            body,
+        } = match self
+            .transport
+            .send(TenorRequest {
+                endpoint: endpoint.clone(),
+                url,
+            })
+            .await
+        {
+            Ok(response) => response,
+            Err(source) => {
+                error!("fetch {endpoint} failed.");
+                return Err(transport::into_error(source, context));
+            }
+        };
+        context.status = Some(status);
+
+        if !status.is_success() {
+            error!("fetch {endpoint} failed with {status}.");
+            return Err(Error::Status {
+                context,
+                body: body_prefix(&body),
+            });
+        }
+        let elapsed = start.elapsed();
+        debug!("fetch {endpoint} successful in {elapsed:?}.");
+
+        Ok(Tracked {
+            meta: ResponseMeta {
+                status,
+                headers,
+                elapsed,
+                url: context.url,
+                bytes: body.len(),
+            },
+            value: body,
+        })
+    }
+
+    /// Fetches pages until `n` unique results passing `filter` are collected or Tenor runs out.
+    /// `start` is returned as `next` when no request is needed.
+    pub(crate) async fn paginate<F, Fut>(
+        &self,
+        n: usize,
+        mut position: Option<Cursor>,
+        start: Cursor,
+        filter: &ResultFilter,
+        mut fetch: F,
+    ) -> Result<search::Response, Error>
+    where
+        F: FnMut(Option<Cursor>, Limit) -> Fut,
+        Fut: Future<Output = Result<search::Response, Error>>,
+    {
+        let mut seen = HashSet::new();
+        let mut results = Vec::new();
+        let mut next = position.clone().unwrap_or(start);
+        // Dropped results would otherwise leave a string of tiny requests, so full pages are fetched and truncated.
+        let dropping = *filter != ResultFilter::default() || self.blocklist.is_some();
+
+        while results.len() < n {
+            let limit = match dropping {
+                true => MAX_LIMIT,
+                false => Limit::try_from(n - results.len())
+                    .map_or(MAX_LIMIT, |limit| limit.min(MAX_LIMIT)),
+            };
+            let page = fetch(position.take(), limit).await?;
+            next = page.next;
+
+            let before = seen.len();
+            results.extend(
+                page.results
+                    .into_iter()
+                    .filter(|object| seen.insert(object.id.clone()))
+                    .filter(|object| filter.matches(object)),
+            );
+            debug!("paginate collected {} of {n} results.", results.len());
+
+            // No unseen results means Tenor is repeating itself.
+            if next.is_end() || seen.len() == before {
+                break;
+            }
+            position = Some(next.clone());
+        }
+        results.truncate(n);
+
+        Ok(search::Response { results, next })
+    }
+
+    /// Locale and country query parameters, preferring the per-request overrides over this instance's locale.
+    fn locale_query_parameter(
+        &self,
+        locale: Option<Locale>,
+        country: Option<CountryCode>,
+    ) -> String {
+        locale.unwrap_or(self.locale).to_query_parameter(country)
+    }
+}
+
+/// Replaces the value of the `key` parameter so urls can be logged and shown.
+pub(crate) fn redact_url(url: &str) -> String {
+    let Some((base, query)) = url.split_once('?') else {
+        return url.to_string();
+    };
+    let query: Vec<&str> = query
+        .split('&')
+        .map(|parameter| match parameter.starts_with("key=") {
+            true => "key=REDACTED",
+            false => parameter,
+        })
+        .collect();
+
+    format!("{base}?{}", query.join("&"))
+}
+
+/// The start of a response body, cut at a character boundary.
+pub(crate) fn body_prefix(body: &str) -> String {
+    let mut end = body.len().min(BODY_PREFIX_LEN);
+    while !body.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    body[..end].to_string()
+}
+
+/// Percent-encodes everything but unreserved characters, for parameters that aren't known ahead of time.
+fn encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                char::from(byte).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
 }
@@ -0,0 +1,577 @@
+//! Test doubles for code built on this crate, enabled with the `test-util` feature.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use reqwest::{
+    StatusCode,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+    task::JoinHandle,
+};
+use tracing::warn;
+
+use crate::{
+    DEFAULT_LIMIT, Error, Limit, Locale, MAX_LIMIT, Tenor, categories,
+    provider::{Category, Gif, GifProvider, Page},
+    search,
+    tenor::redact_url,
+    transport::{BoxError, ReplayError, TenorRequest, TenorResponse, Transport},
+};
+
+/// In-memory [`GifProvider`] serving the GIFs, terms and categories it was given.
+///
+/// Searches match the query against titles, descriptions and tags in any case.
+/// Positions are offsets into the matches, and shares are recorded for [`FakeProvider::shares`].
+#[derive(Debug, Clone, Default)]
+pub struct FakeProvider {
+    gifs: Vec<Gif>,
+    trending_terms: Vec<String>,
+    categories: Vec<Category>,
+    shares: Arc<Mutex<Vec<Share>>>,
+}
+
+/// A call to [`GifProvider::register_share`] seen by a [`FakeProvider`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Share {
+    pub id: String,
+    pub query: Option<String>,
+}
+
+impl FakeProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds GIFs, also accepting [`search::ResponseObject`]s.
+    #[must_use]
+    pub fn with_gifs<I, G>(mut self, gifs: I) -> Self
+    where
+        I: IntoIterator<Item = G>,
+        G: Into<Gif>,
+    {
+        self.gifs.extend(gifs.into_iter().map(Into::into));
+        self
+    }
+
+    #[must_use]
+    pub fn with_trending_terms<I, S>(mut self, terms: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.trending_terms
+            .extend(terms.into_iter().map(Into::into));
+        self
+    }
+
+    #[must_use]
+    pub fn with_categories(mut self, categories: impl IntoIterator<Item = Category>) -> Self {
+        self.categories.extend(categories);
+        self
+    }
+
+    /// Every share registered so far, shared between clones.
+    pub fn shares(&self) -> Vec<Share> {
+        self.shares
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .clone()
+    }
+
+    fn page<'a>(
+        &self,
+        gifs: impl Iterator<Item = &'a Gif>,
+        limit: Limit,
+        position: Option<&str>,
+    ) -> Page {
+        let start = position
+            .and_then(|position| position.parse().ok())
+            .unwrap_or(0);
+        let mut gifs = gifs.skip(start);
+        let page: Vec<Gif> = gifs.by_ref().take(limit.into()).cloned().collect();
+        let next = gifs
+            .next()
+            .is_some()
+            .then(|| (start + page.len()).to_string());
+
+        Page { gifs: page, next }
+    }
+}
+
+impl GifProvider for FakeProvider {
+    async fn search(
+        &self,
+        query: &str,
+        limit: Limit,
+        position: Option<&str>,
+    ) -> Result<Page, Error> {
+        let query = query.to_lowercase();
+        let matches = self.gifs.iter().filter(|gif| {
+            [&gif.title, &gif.description]
+                .into_iter()
+                .chain(&gif.tags)
+                .any(|text| text.to_lowercase().contains(&query))
+        });
+
+        Ok(self.page(matches, limit, position))
+    }
+
+    async fn featured(&self, limit: Limit, position: Option<&str>) -> Result<Page, Error> {
+        Ok(self.page(self.gifs.iter(), limit, position))
+    }
+
+    async fn trending_terms(&self, limit: Limit) -> Result<Vec<String>, Error> {
+        Ok(self
+            .trending_terms
+            .iter()
+            .take(limit.into())
+            .cloned()
+            .collect())
+    }
+
+    async fn categories(&self) -> Result<Vec<Category>, Error> {
+        Ok(self.categories.clone())
+    }
+
+    async fn register_share(&self, id: &str, query: Option<&str>) -> Result<(), Error> {
+        self.shares
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(Share {
+                id: id.to_string(),
+                query: query.map(str::to_string),
+            });
+
+        Ok(())
+    }
+}
+
+/// Records real api responses to a file and serves them back, so tests run offline and always see the same data.
+///
+/// Record once with [`Cassette::record`], which needs a real api key, and [`Cassette::save`] the result.
+/// Later runs use [`Cassette::replay`] with any key. Urls are stored with the api key replaced by `REDACTED`
+/// and a replayed request must match a recorded one exactly, identical requests are answered in recorded order.
+#[derive(Debug, Clone)]
+pub struct Cassette {
+    path: PathBuf,
+    recording: bool,
+    interactions: Arc<Mutex<Vec<Interaction>>>,
+}
+
+/// One recorded request and Tenor's answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub endpoint: String,
+    /// The request url with the api key replaced by `REDACTED`.
+    pub url: String,
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// Set once replayed, so the next identical request gets the next recording.
+    #[serde(skip)]
+    replayed: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CassetteFile {
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Starts an empty recording that [`Cassette::save`] writes to `path`.
+    pub fn record(path: impl AsRef<Path>) -> Self {
+        Self {
+            path: path.as_ref().to_path_buf(),
+            recording: true,
+            interactions: Arc::default(),
+        }
+    }
+
+    /// Loads the recording at `path`.
+    pub fn replay(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let file: CassetteFile = serde_json::from_str(&fs::read_to_string(&path)?)?;
+
+        Ok(Self {
+            path: path.as_ref().to_path_buf(),
+            recording: false,
+            interactions: Arc::new(Mutex::new(file.interactions)),
+        })
+    }
+
+    /// Returns a [`Tenor`] that records to or replays from this cassette.
+    pub fn tenor(&self, api_key: impl AsRef<str>, locale: Locale) -> Tenor {
+        let transport = match self.recording {
+            true => self.recorder(Transport::http(reqwest::Client::new())),
+            false => self.player(),
+        };
+
+        Tenor::with_transport(api_key, locale, transport)
+    }
+
+    /// Writes everything recorded so far, replaying cassettes are written back unchanged.
+    pub fn save(&self) -> Result<(), Error> {
+        let file = CassetteFile {
+            interactions: self.interactions(),
+        };
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&file)?)?;
+
+        Ok(())
+    }
+
+    /// Everything recorded or loaded so far, shared between clones.
+    pub fn interactions(&self) -> Vec<Interaction> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<Interaction>> {
+        self.interactions
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    pub(crate) fn recorder(&self, inner: Transport) -> Transport {
+        let cassette = self.clone();
+        Transport::from_fn(move |request: TenorRequest| {
+            let inner = inner.clone();
+            let cassette = cassette.clone();
+            async move {
+                let endpoint = request.endpoint.clone();
+                let url = redact_url(&request.url);
+                let response = inner.send(request).await?;
+                cassette.lock().push(Interaction {
+                    endpoint: endpoint.into_owned(),
+                    url,
+                    status: response.status.as_u16(),
+                    headers: response
+                        .headers
+                        .iter()
+                        .filter_map(|(name, value)| {
+                            Some((name.to_string(), value.to_str().ok()?.to_string()))
+                        })
+                        .collect(),
+                    body: response.body.clone(),
+                    replayed: false,
+                });
+
+                Ok(response)
+            }
+        })
+    }
+
+    fn player(&self) -> Transport {
+        let cassette = self.clone();
+        Transport::from_fn(move |request: TenorRequest| {
+            let response = cassette
+                .play(&request)
+                .map_err(|err| BoxError::from(ReplayError(err)));
+            async move { response }
+        })
+    }
+
+    fn play(&self, request: &TenorRequest) -> Result<TenorResponse, String> {
+        let url = redact_url(&request.url);
+        let mut interactions = self.lock();
+        let interaction = interactions
+            .iter_mut()
+            .find(|interaction| {
+                !interaction.replayed
+                    && interaction.endpoint == request.endpoint
+                    && interaction.url == url
+            })
+            .ok_or_else(|| format!("no recorded response left for {url}"))?;
+        let invalid =
+            |err: &dyn std::fmt::Display| format!("the recording of {url} is invalid: {err}");
+        interaction.replayed = true;
+
+        let mut headers = HeaderMap::new();
+        for (name, value) in &interaction.headers {
+            headers.append(
+                HeaderName::try_from(name.as_str()).map_err(|err| invalid(&err))?,
+                HeaderValue::try_from(value.as_str()).map_err(|err| invalid(&err))?,
+            );
+        }
+
+        Ok(TenorResponse {
+            status: StatusCode::from_u16(interaction.status).map_err(|err| invalid(&err))?,
+            headers,
+            body: interaction.body.clone(),
+        })
+    }
+}
+
+/// Wait before accepting again after a failure, doubled up to [`MAX_ACCEPT_BACKOFF`].
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(10);
+
+const MAX_ACCEPT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// A fake Tenor api on localhost, for integration tests that run a whole service.
+///
+/// Serves `/v2/search`, `/v2/featured`, `/v2/categories` and `/v2/trending_terms` from the seeded data
+/// and understands the parameters [`Tenor`] sends. `pos` is an offset into the matching results.
+/// Point clients at [`MockTenor::url`] with [`Tenor::with_base_url`], or use [`MockTenor::tenor`].
+/// The server stops when this is dropped.
+#[derive(Debug)]
+pub struct MockTenor {
+    url: String,
+    state: Arc<Mutex<MockState>>,
+    server: JoinHandle<()>,
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    results: Vec<search::ResponseObject>,
+    categories: Vec<categories::Tag>,
+    trending_terms: Vec<String>,
+    errors: VecDeque<(StatusCode, String)>,
+    latency: Duration,
+    requests: Vec<String>,
+}
+
+impl MockTenor {
+    /// Starts the server on a free port.
+    pub async fn start() -> Result<Self, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let url = format!("http://{}/v2", listener.local_addr()?);
+        let state = Arc::new(Mutex::new(MockState::default()));
+        let server = tokio::spawn(serve(listener, state.clone()));
+
+        Ok(Self { url, state, server })
+    }
+
+    /// The base url to pass to [`Tenor::with_base_url`].
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns a [`Tenor`] talking to this server.
+    pub fn tenor(&self, locale: Locale) -> Tenor {
+        Tenor::new("MOCK_KEY", locale).with_base_url(&self.url)
+    }
+
+    /// Adds results served by search and featured, in order.
+    pub fn seed_results(&self, results: impl IntoIterator<Item = search::ResponseObject>) {
+        self.lock().results.extend(results);
+    }
+
+    pub fn seed_categories(&self, categories: impl IntoIterator<Item = categories::Tag>) {
+        self.lock().categories.extend(categories);
+    }
+
+    pub fn seed_trending_terms<I, S>(&self, terms: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.lock()
+            .trending_terms
+            .extend(terms.into_iter().map(Into::into));
+    }
+
+    /// Answers the next request, whatever it is, with `status` and `body`. Queued errors are served in order.
+    pub fn fail_next(&self, status: StatusCode, body: impl Into<String>) {
+        self.lock().errors.push_back((status, body.into()));
+    }
+
+    /// Waits this long before every response.
+    pub fn set_latency(&self, latency: Duration) {
+        self.lock().latency = latency;
+    }
+
+    /// Paths and queries of every request received so far, with the api key replaced by `REDACTED`.
+    pub fn requests(&self) -> Vec<String> {
+        self.lock().requests.clone()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, MockState> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drop for MockTenor {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+impl MockState {
+    fn respond(&mut self, target: &str) -> (StatusCode, String) {
+        if let Some(error) = self.errors.pop_front() {
+            return error;
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let parameters: HashMap<String, String> = query
+            .split('&')
+            .filter_map(|parameter| parameter.split_once('='))
+            .map(|(name, value)| (decode(name), decode(value)))
+            .collect();
+        if parameters.get("key").is_none_or(String::is_empty) {
+            let error = json!({
+                "error": {
+                    "code": 400,
+                    "message": "API key not valid. Please pass a valid API key.",
+                    "details": [{ "reason": "API_KEY_INVALID" }],
+                }
+            });
+            return (StatusCode::BAD_REQUEST, error.to_string());
+        }
+
+        let limit = parameters
+            .get("limit")
+            .and_then(|limit| limit.parse().ok())
+            .unwrap_or(DEFAULT_LIMIT)
+            .min(MAX_LIMIT);
+        let position = parameters
+            .get("pos")
+            .and_then(|position| position.parse().ok())
+            .unwrap_or(0);
+        let locale: Locale = parameters
+            .get("locale")
+            .and_then(|locale| locale.parse().ok())
+            .unwrap_or_default();
+
+        let body = match path {
+            "/v2/search" => {
+                let query = parameters
+                    .get("q")
+                    .map(|query| query.to_lowercase())
+                    .unwrap_or_default();
+                let matches = self.results.iter().filter(|object| {
+                    [&object.title, &object.content_description]
+                        .into_iter()
+                        .chain(&object.tags)
+                        .any(|text| text.to_lowercase().contains(&query))
+                });
+                page(matches, limit, position)
+            }
+            "/v2/featured" => page(self.results.iter(), limit, position),
+            "/v2/categories" => json!({
+                "locale": locale.language(),
+                "tags": self.categories,
+            }),
+            "/v2/trending_terms" => json!({
+                "locale": locale.to_string(),
+                "results": self.trending_terms.iter().take(limit.into()).collect::<Vec<_>>(),
+            }),
+            _ => {
+                let error =
+                    json!({ "error": { "code": 404, "message": format!("{path} not found") } });
+                return (StatusCode::NOT_FOUND, error.to_string());
+            }
+        };
+
+        (StatusCode::OK, body.to_string())
+    }
+}
+
+fn page<'a>(
+    results: impl Iterator<Item = &'a search::ResponseObject>,
+    limit: Limit,
+    position: usize,
+) -> serde_json::Value {
+    let mut results = results.skip(position);
+    let page: Vec<_> = results.by_ref().take(limit.into()).collect();
+    let next = match results.next() {
+        Some(_) => (position + page.len()).to_string(),
+        None => String::new(),
+    };
+
+    json!({ "results": page, "next": next })
+}
+
+/// Accepts connections until dropped, waiting longer after each failed accept, such as running out of file descriptors.
+async fn serve(listener: TcpListener, state: Arc<Mutex<MockState>>) {
+    let mut backoff = ACCEPT_BACKOFF;
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                backoff = ACCEPT_BACKOFF;
+                tokio::spawn(handle(stream, state.clone()));
+            }
+            Err(err) => {
+                warn!("mock tenor failed to accept a connection, retrying in {backoff:?}: {err}");
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_ACCEPT_BACKOFF);
+            }
+        }
+    }
+}
+
+/// Answers one GET request and closes the connection.
+async fn handle(mut stream: TcpStream, state: Arc<Mutex<MockState>>) -> io::Result<()> {
+    let mut head = Vec::new();
+    let mut chunk = [0; 1024];
+    while !head.windows(4).any(|window| window == b"\r\n\r\n") {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        head.extend_from_slice(&chunk[..read]);
+    }
+    let head = String::from_utf8_lossy(&head);
+    let target = head.split_whitespace().nth(1).unwrap_or("/");
+
+    let (latency, (status, body)) = {
+        let mut state = state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        state.requests.push(redact_url(target));
+        (state.latency, state.respond(target))
+    };
+    tokio::time::sleep(latency).await;
+
+    let response = format!(
+        "HTTP/1.1 {} {}\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or_default(),
+        body.len(),
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+/// Undoes the percent and `+` encoding of a query parameter.
+fn decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (b'+', _) => {
+                decoded.push(b' ');
+                i += 1;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
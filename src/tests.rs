@@ -1,7 +1,6 @@
 use std::env;
 
 use dotenv::dotenv;
-use tokio;
 
 use crate::*;
 
@@ -54,7 +53,7 @@ async fn search_parameters() {
 
     let parms = search::Parameters {
         content_filter: ContentFilter::Medium,
-        media_filter: Some(&[MediaFilter::Gif, MediaFilter::Mp4]),
+        media_filter: Some(MediaFilter::Gif | MediaFilter::Mp4),
         ar_range: ArRange::Standard,
         random: true,
         limit: 2,
@@ -81,3 +80,1368 @@ async fn trending() {
     dbg!("{}", &response);
     assert!(response.is_ok())
 }
+
+#[tokio::test]
+async fn download() {
+    let _ = dotenv();
+    let tenor = tenor::Tenor::new(
+        env::var("API_KEY").expect("Failed to find env file"),
+        Locale::default(),
+    );
+    let response = tenor.featured().await.expect("Failed to fetch featured");
+    let objects = &response.results[..2];
+
+    let dir = env::temp_dir().join("tenor-download");
+    std::fs::create_dir_all(&dir).expect("Failed to create download directory");
+    let downloads = tenor
+        .downloader(MediaFilter::TinyGif)
+        .to_dir(objects, &dir)
+        .await;
+
+    dbg!("{}", &downloads);
+    assert_eq!(downloads.len(), objects.len());
+    assert!(downloads.iter().all(|download| download.result.is_ok()))
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn download_errors() {
+    let mut object = fixture_objects().swap_remove(0);
+    let mock = testing::MockTenor::start().await.unwrap();
+    let downloader = mock
+        .tenor(Locale::default())
+        .downloader(MediaFilter::Gif)
+        .retries(0);
+
+    let url = format!("{}/missing.gif", mock.url());
+    mock.fail_next(StatusCode::NOT_FOUND, "gone");
+    object.media_formats.gif.as_mut().unwrap().url = url.clone();
+    let err = downloader
+        .to_writer(&object, &mut Vec::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Status { .. }));
+    assert!(err.is_not_found());
+    assert_eq!(err.context().unwrap().endpoint, "download");
+    assert_eq!(err.context().unwrap().url, url);
+
+    object.media_formats.gif.as_mut().unwrap().url = "http://127.0.0.1:1/x.gif".to_string();
+    let err = downloader
+        .to_writer(&object, &mut Vec::new())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Send { .. }));
+    assert!(err.is_retryable());
+}
+
+#[test]
+fn download_file_name() {
+    use download::file_name;
+
+    assert_eq!(
+        file_name("8776030", "https://media.tenor.com/abc/tenor.gif").unwrap(),
+        "8776030.gif"
+    );
+    assert_eq!(
+        file_name("8776030", "https://media.tenor.com/abc/tenor.mp4?v=1&x=a.b").unwrap(),
+        "8776030.mp4"
+    );
+    assert_eq!(
+        file_name("8776030", "https://media.tenor.com/abc/tenor").unwrap(),
+        "8776030.bin"
+    );
+    for id in ["../../x", "a/b", "a\\b", "..", ""] {
+        assert!(matches!(
+            file_name(id, "https://media.tenor.com/a.gif"),
+            Err(Error::UnsafeId(_))
+        ));
+    }
+}
+
+#[test]
+fn locale_from_str() {
+    assert_eq!(
+        "ja_JP".parse(),
+        Ok(Locale::new(Language::Japanese, CountryCode::JP))
+    );
+    assert_eq!(
+        "pt-br".parse(),
+        Ok(Locale::new(Language::Portuguese, CountryCode::BR))
+    );
+    assert_eq!("en".parse(), Ok(Locale::from_language(Language::English)));
+    assert_eq!(
+        "en_XX".parse::<Locale>(),
+        Err(LocaleError::InvalidCountry("XX".to_string()))
+    );
+    assert_eq!(
+        "english".parse::<Locale>(),
+        Err(LocaleError::Malformed("english".to_string()))
+    );
+}
+
+#[test]
+fn locale_negotiate() {
+    let supported = [
+        Locale::default(),
+        Locale::new(Language::Portuguese, CountryCode::PT),
+        Locale::new(Language::Japanese, CountryCode::JP),
+    ];
+
+    assert_eq!(
+        Locale::negotiate("pt-BR,pt;q=0.9,en;q=0.8", &supported),
+        Some(Locale::new(Language::Portuguese, CountryCode::PT))
+    );
+    assert_eq!(
+        Locale::negotiate("de;q=0.9, ja-JP;q=0.5", &supported),
+        Some(Locale::new(Language::Japanese, CountryCode::JP))
+    );
+    assert_eq!(Locale::negotiate("de", &supported), None);
+    assert_eq!(
+        Locale::negotiate("de, *;q=0.1", &supported),
+        Some(Locale::default())
+    );
+}
+
+#[test]
+fn categories_url() {
+    let tenor = tenor::Tenor::new("KEY", Locale::default());
+
+    assert_eq!(
+        tenor.categories_url(categories::Type::Featured, None),
+        "https://tenor.googleapis.com/v2/categories?key=KEY&country=US&locale=en_US&type=featured"
+    );
+
+    let parms = categories::Parameters {
+        client_key: "my_test_app".to_string(),
+        content_filter: ContentFilter::High,
+        ..Default::default()
+    };
+    assert_eq!(
+        tenor.categories_url(categories::Type::Trending, Some(&parms)),
+        "https://tenor.googleapis.com/v2/categories?key=KEY&country=US&locale=en_US&type=trending\
+        &client_key=my_test_app&contentfilter=high"
+    );
+}
+
+#[test]
+fn featured_url() {
+    let tenor = tenor::Tenor::new("KEY", Locale::default());
+
+    assert_eq!(
+        tenor.featured_url(None),
+        "https://tenor.googleapis.com/v2/featured?key=KEY&country=US&locale=en_US"
+    );
+
+    let parms = featured::Parameters {
+        client_key: "my_test_app".to_string(),
+        search_filter: Some(SearchFilter::NonStatic),
+        media_filter: Some(MediaFilter::WebpTransparent | MediaFilter::TinyGifTransparent),
+        ar_range: ArRange::Wide,
+        content_filter: ContentFilter::Low,
+        limit: 10,
+        position: Some(Cursor::featured("CAgQ".to_string())),
+        locale: None,
+        country: Some(CountryCode::GB),
+    };
+    assert_eq!(
+        tenor.featured_url(Some(&parms)),
+        "https://tenor.googleapis.com/v2/featured?key=KEY&country=GB&locale=en_US&client_key=my_test_app\
+        &searchfilter=sticker,-static&media_filter=webp_transparent,tinygif_transparent\
+        &ar_range=wide&contentfilter=low&limit=10&pos=CAgQ"
+    );
+}
+
+#[test]
+fn search_url() {
+    let tenor = tenor::Tenor::new("KEY", Locale::default());
+
+    assert_eq!(
+        tenor.search_url("excited", None),
+        "https://tenor.googleapis.com/v2/search?q=excited&key=KEY&country=US&locale=en_US"
+    );
+
+    let parms = search::Parameters {
+        content_filter: ContentFilter::Medium,
+        media_filter: Some(MediaFilter::Gif | MediaFilter::Mp4),
+        ar_range: ArRange::Standard,
+        random: true,
+        limit: 2,
+        locale: Some(Locale::new(Language::Japanese, CountryCode::JP)),
+        ..Default::default()
+    };
+    assert_eq!(
+        tenor.search_url("excited", Some(&parms)),
+        "https://tenor.googleapis.com/v2/search?q=excited&key=KEY&country=JP&locale=ja_JP&client_key=my_test_app\
+        &contentfilter=medium&media_filter=gif,mp4&ar_range=standard&random=true&limit=2"
+    );
+
+    // Spaces, `&` and `#` must not end the parameter or start a fragment.
+    for (query, encoded) in [
+        ("rock & roll", "rock%20%26%20roll"),
+        ("c#", "c%23"),
+        ("a=b&key=OTHER", "a%3Db%26key%3DOTHER"),
+    ] {
+        assert_eq!(
+            tenor.search_url(query, None),
+            format!(
+                "https://tenor.googleapis.com/v2/search?q={encoded}&key=KEY&country=US&locale=en_US"
+            )
+        );
+    }
+    let parms = search::Parameters {
+        client_key: "my app&x".into(),
+        ..Default::default()
+    };
+    assert!(
+        tenor
+            .search_url("c#", Some(&parms))
+            .starts_with("https://tenor.googleapis.com/v2/search?q=c%23&key=KEY&country=US&locale=en_US&client_key=my%20app%26x&")
+    );
+
+    // A cursor sent back by a client can't add or override parameters.
+    let parms = search::Parameters {
+        position: Some(Cursor::search("cats", "a&key=OTHER&limit=50".to_string())),
+        ..Default::default()
+    };
+    assert!(
+        tenor
+            .search_url("cats", Some(&parms))
+            .ends_with("&limit=20&pos=a%26key%3DOTHER%26limit%3D50")
+    );
+    let parms = featured::Parameters {
+        position: Some(Cursor::featured("a&key=OTHER".to_string())),
+        ..Default::default()
+    };
+    assert!(
+        tenor
+            .featured_url(Some(&parms))
+            .ends_with("&pos=a%26key%3DOTHER")
+    );
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn position_is_encoded() {
+    let mock = testing::MockTenor::start().await.unwrap();
+    let tenor = mock.tenor(Locale::default());
+
+    let cursor = Cursor::search("cats", "a&key=OTHER".to_string());
+    tenor.search_with_position("cats", &cursor).await.unwrap();
+    let cursor = Cursor::featured("a&limit=50".to_string());
+    tenor.featured_with_position(&cursor).await.unwrap();
+
+    let requests = mock.requests();
+    assert!(requests[0].ends_with("&pos=a%26key%3DOTHER"));
+    assert!(requests[1].ends_with("&pos=a%26limit%3D50"));
+    assert!(
+        requests
+            .iter()
+            .all(|request| request.matches("key=").count() == 1)
+    );
+}
+
+#[test]
+fn trending_terms_url() {
+    let tenor = tenor::Tenor::new("KEY", Locale::from_language(Language::Portuguese));
+
+    assert_eq!(
+        tenor.trending_terms_url(None),
+        "https://tenor.googleapis.com/v2/trending_terms?key=KEY&locale=pt"
+    );
+
+    let parms = trending::Parameters {
+        client_key: "my_test_app".to_string(),
+        limit: 5,
+        country: Some(CountryCode::BR),
+        ..Default::default()
+    };
+    assert_eq!(
+        tenor.trending_terms_url(Some(&parms)),
+        "https://tenor.googleapis.com/v2/trending_terms?key=KEY&country=BR&locale=pt&client_key=my_test_app&limit=5"
+    );
+}
+
+#[test]
+fn json_url() {
+    let tenor = tenor::Tenor::new("KEY", Locale::default());
+
+    assert_eq!(
+        tenor.json_url("autocomplete", &[("q", "happy b"), ("limit", "5")]),
+        "https://tenor.googleapis.com/v2/autocomplete?key=KEY&country=US&locale=en_US&q=happy%20b&limit=5"
+    );
+}
+
+#[test]
+fn borrowed_response() {
+    let body = include_str!("../benches/fixtures/search.json");
+    let response = borrowed::Response::parse(body).expect("Failed to parse");
+    let owned = fixture_objects();
+
+    assert_eq!(response.results.len(), 50);
+    assert_eq!(response.next, "CDIQ");
+    let first = &response.results[0];
+    assert!(matches!(first.id, std::borrow::Cow::Borrowed(_)));
+    assert!(matches!(first.tags[0], std::borrow::Cow::Borrowed(_)));
+    assert!(
+        first
+            .media_formats
+            .get(&MediaFilter::Gif)
+            .is_some_and(|format| matches!(format.url, std::borrow::Cow::Borrowed(_)))
+    );
+    // Escaped titles can't point into the body.
+    assert!(
+        response
+            .results
+            .iter()
+            .any(|object| matches!(object.title, std::borrow::Cow::Owned(_)))
+    );
+
+    let converted: Vec<_> = response
+        .results
+        .into_iter()
+        .map(borrowed::ResponseObject::into_owned)
+        .collect();
+    assert_eq!(converted, owned);
+
+    // Anything the owned model reads parses here too, whatever the length of `dims`.
+    let mut body: serde_json::Value = serde_json::from_str(body).unwrap();
+    body["results"][0]["media_formats"]["gif"]["dims"] = serde_json::json!([640]);
+    body["results"][0]["media_formats"]["gifpreview"]["dims"] = serde_json::json!([]);
+    let owned: search::ResponseObject =
+        serde_json::from_value(body["results"][0].clone()).expect("Failed to parse");
+    let body = body.to_string();
+    let response = borrowed::Response::parse(&body).expect("Failed to parse");
+    assert_eq!(response.results[0].clone().into_owned(), owned);
+}
+
+#[test]
+fn register_share_url() {
+    let tenor = tenor::Tenor::new("KEY", Locale::default());
+
+    assert_eq!(
+        tenor.register_share_url("8776030", Some("happy birthday")),
+        "https://tenor.googleapis.com/v2/registershare?id=8776030&key=KEY&country=US&locale=en_US&q=happy%20birthday"
+    );
+    assert_eq!(
+        tenor.register_share_url("8776030", None),
+        "https://tenor.googleapis.com/v2/registershare?id=8776030&key=KEY&country=US&locale=en_US"
+    );
+}
+
+#[test]
+fn provider_gif() {
+    let object = fixture_objects().swap_remove(0);
+    let gif = provider::Gif::from(&object);
+
+    assert_eq!(gif.id, object.id);
+    assert_eq!(gif.tags, object.tags);
+    let rendition = gif
+        .rendition(provider::MediaKind::Gif)
+        .expect("Missing gif rendition");
+    assert_eq!(
+        rendition.url,
+        object.media_formats.gif.as_ref().unwrap().url
+    );
+    assert!(
+        gif.renditions
+            .iter()
+            .filter(|rendition| rendition.kind == provider::MediaKind::Gif)
+            .all(|other| other.width <= rendition.width)
+    );
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn fake_provider() {
+    let objects = fixture_objects();
+    let query = objects[0].tags[0].to_uppercase();
+    let provider = testing::FakeProvider::new()
+        .with_gifs(objects)
+        .with_trending_terms(["cats", "dogs", "birds"]);
+
+    let first = provider.search(&query, 1, None).await.unwrap();
+    assert_eq!(first.gifs.len(), 1);
+    let featured = provider.featured(50, None).await.unwrap();
+    assert_eq!(featured.gifs.len(), 50);
+    assert_eq!(featured.next, None);
+    let next = provider.featured(20, Some("40")).await.unwrap();
+    assert_eq!(next.gifs.len(), 10);
+    assert_eq!(next.gifs[0], featured.gifs[40]);
+    assert_eq!(provider.trending_terms(2).await.unwrap(), ["cats", "dogs"]);
+
+    provider
+        .register_share(&first.gifs[0].id, Some(&query))
+        .await
+        .unwrap();
+    assert_eq!(
+        provider.clone().shares(),
+        [testing::Share {
+            id: first.gifs[0].id.clone(),
+            query: Some(query),
+        }]
+    );
+}
+
+#[cfg(feature = "tower")]
+#[tokio::test]
+async fn tower_service() {
+    use std::time::Duration;
+
+    use tower::{ServiceBuilder, service_fn};
+    use transport::{TenorRequest, TenorResponse};
+
+    let service = service_fn(|request: TenorRequest| async move {
+        assert_eq!(request.endpoint, "search");
+        assert!(request.url.contains("key=KEY"));
+        Ok::<_, transport::BoxError>(TenorResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::new(),
+            body: include_str!("../benches/fixtures/search.json").to_string(),
+        })
+    });
+    // `BoxCloneService` is `Send` but not `Sync`.
+    let service = tower::util::BoxCloneService::new(service);
+    let tenor = Tenor::with_service("KEY", Locale::default(), service);
+    let response = tenor
+        .search_with_meta("cats", Default::default())
+        .await
+        .unwrap();
+    assert_eq!(response.value.results.len(), 50);
+    assert_eq!(
+        response.meta.url,
+        tenor
+            .search_url("cats", Some(&Default::default()))
+            .replace("KEY", "REDACTED")
+    );
+
+    let slow = ServiceBuilder::new()
+        .timeout(Duration::from_millis(10))
+        .service(service_fn(|_: TenorRequest| async {
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            Ok::<_, transport::BoxError>(TenorResponse {
+                status: StatusCode::OK,
+                headers: HeaderMap::new(),
+                body: String::new(),
+            })
+        }));
+    let tenor = Tenor::with_service("KEY", Locale::default(), slow);
+    let err = tenor.search("cats").await.unwrap_err();
+    assert!(matches!(err, Error::Transport { .. }));
+    assert!(err.is_retryable());
+    assert_eq!(err.context().unwrap().endpoint, "search");
+    assert!(!err.to_string().contains("KEY"));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn cassette() {
+    let path = env::temp_dir().join(format!("tenor-cassette-{}.json", std::process::id()));
+    let recording = testing::Cassette::record(&path);
+    let live = transport::Transport::from_fn(|_| async {
+        Ok(transport::TenorResponse {
+            status: StatusCode::OK,
+            headers: HeaderMap::from_iter([(
+                reqwest::header::CONTENT_TYPE,
+                "application/json".parse().unwrap(),
+            )]),
+            body: include_str!("../benches/fixtures/search.json").to_string(),
+        })
+    });
+    let tenor = Tenor::with_transport("SECRET", Locale::default(), recording.recorder(live));
+    let recorded = tenor
+        .search_with_meta("cats", Default::default())
+        .await
+        .unwrap()
+        .into_inner();
+    recording.save().unwrap();
+    assert!(!std::fs::read_to_string(&path).unwrap().contains("SECRET"));
+
+    let replaying = testing::Cassette::replay(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let tenor = replaying.tenor("OTHER", Locale::default());
+    let replayed = tenor
+        .search_with_meta("cats", Default::default())
+        .await
+        .unwrap();
+    assert_eq!(replayed.value, recorded);
+    assert_eq!(
+        replayed.meta.headers[reqwest::header::CONTENT_TYPE],
+        "application/json"
+    );
+
+    // Each recording is served once and other requests never match, retrying can't help.
+    let err = tenor
+        .search_with_meta("cats", Default::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Replay { .. }));
+    assert!(!err.is_retryable());
+    let err = tenor.search("dogs").await.unwrap_err();
+    assert!(matches!(err, Error::Replay { .. }));
+    assert!(!err.is_retryable());
+
+    // Broken recordings are reported the same way.
+    let mut interaction = replaying.interactions()[0].clone();
+    interaction.status = 1000;
+    std::fs::write(
+        &path,
+        serde_json::json!({ "interactions": [interaction] }).to_string(),
+    )
+    .unwrap();
+    let broken = testing::Cassette::replay(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    let err = broken
+        .tenor("OTHER", Locale::default())
+        .search_with_meta("cats", Default::default())
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::Replay { .. }));
+    assert!(!err.is_retryable());
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn mock_tenor() {
+    use std::time::Duration;
+
+    let objects = fixture_objects();
+    let mock = testing::MockTenor::start().await.unwrap();
+    mock.seed_results(objects.clone());
+    mock.seed_trending_terms(["cats", "dogs"]);
+    mock.seed_categories([categories::Tag {
+        searchterm: "excited".to_string(),
+        path: "/v2/search?q=excited".to_string(),
+        image: "https://media.tenor.com/excited.gif".to_string(),
+        name: "#excited".to_string(),
+    }]);
+    let tenor = mock.tenor(Locale::default());
+
+    let first = tenor.featured().await.unwrap();
+    assert_eq!(first.results, objects[..20]);
+    let parms = featured::Parameters::builder()
+        .position(first.next)
+        .limit(40)
+        .build()
+        .unwrap();
+    let rest = tenor.featured_with_parameters(parms).await.unwrap();
+    assert_eq!(rest.results, objects[20..]);
+    assert!(rest.next.is_end());
+
+    let query = objects[3].tags[0].to_uppercase();
+    let found = tenor.search(&query).await.unwrap();
+    assert!(found.results.contains(&objects[3]));
+    assert!(
+        mock.requests()
+            .iter()
+            .all(|request| request.contains("key=REDACTED"))
+    );
+
+    assert_eq!(
+        tenor.trending_terms().await.unwrap().results,
+        ["cats", "dogs"]
+    );
+    assert_eq!(
+        tenor.categories_featured().await.unwrap().tags[0].name,
+        "#excited"
+    );
+
+    mock.fail_next(StatusCode::TOO_MANY_REQUESTS, "slow down");
+    assert!(tenor.search("cats").await.unwrap_err().is_rate_limited());
+    assert!(
+        Tenor::new("", Locale::default())
+            .with_base_url(mock.url())
+            .search("cats")
+            .await
+            .unwrap_err()
+            .is_auth()
+    );
+
+    mock.set_latency(Duration::from_millis(50));
+    let slow = tenor
+        .search_with_meta("cats", Default::default())
+        .await
+        .unwrap();
+    assert!(slow.meta.elapsed >= Duration::from_millis(50));
+    mock.set_latency(Duration::ZERO);
+
+    // Cassettes record over real http.
+    let path = env::temp_dir().join(format!("tenor-mock-cassette-{}.json", std::process::id()));
+    let recording = testing::Cassette::record(&path);
+    let recorded = recording
+        .tenor("SECRET", Locale::default())
+        .with_base_url(mock.url())
+        .search("cats")
+        .await
+        .unwrap();
+    recording.save().unwrap();
+    let url = mock.url().to_string();
+    drop(mock);
+    let replayed = testing::Cassette::replay(&path)
+        .unwrap()
+        .tenor("OTHER", Locale::default())
+        .with_base_url(url);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(replayed.search("cats").await.unwrap(), recorded);
+}
+
+#[test]
+fn media_filters() {
+    let mut filters: MediaFilters = "mp4, gif,gif,".parse().expect("Failed to parse filters");
+    assert_eq!(filters, MediaFilter::Gif | MediaFilter::Mp4);
+    assert_eq!(filters.to_string(), "gif,mp4");
+    assert!(!filters.insert(MediaFilter::Gif));
+    assert!(filters.insert(MediaFilter::TinyGif));
+
+    let videos = MediaFilter::Mp4 | MediaFilter::Webm;
+    assert_eq!((filters & videos).to_string(), "mp4");
+    assert_eq!((filters - videos).to_string(), "gif,tinygif");
+    assert_eq!((filters | videos).len(), 4);
+    assert_eq!(!MediaFilters::EMPTY, MediaFilters::ALL);
+    assert_eq!(
+        "gif,jpeg".parse::<MediaFilters>(),
+        Err(ParseError::new("media filter", "jpeg"))
+    );
+
+    let json = serde_json::to_string(&filters).expect("Failed to serialize filters");
+    assert_eq!(json, r#""gif,tinygif,mp4""#);
+    assert_eq!(
+        serde_json::from_str::<MediaFilters>(&json).ok(),
+        Some(filters)
+    );
+}
+
+#[test]
+fn request_enums_from_str() {
+    assert_eq!("wide".parse(), Ok(ArRange::Wide));
+    assert_eq!("high".parse(), Ok(ContentFilter::High));
+    assert_eq!("sticker,-static".parse(), Ok(SearchFilter::NonStatic));
+    assert_eq!(
+        "tinygif_transparent".parse(),
+        Ok(MediaFilter::TinyGifTransparent)
+    );
+    assert_eq!(
+        "extreme".parse::<ContentFilter>(),
+        Err(ParseError::new("content filter", "extreme"))
+    );
+
+    let parms = search::Parameters {
+        search_filter: Some(SearchFilter::Static),
+        locale: Some(Locale::new(Language::Japanese, CountryCode::JP)),
+        ..Default::default()
+    };
+    let json = serde_json::to_string(&parms).expect("Failed to serialize parameters");
+    assert_eq!(
+        serde_json::from_str::<search::Parameters>(&json).ok(),
+        Some(parms)
+    );
+}
+
+#[cfg(feature = "presets")]
+#[test]
+fn search_preset() {
+    let config = r#"
+        [reactions]
+        content_filter = "high"
+        media_filter = "gif,tinygif"
+        limit = 10
+
+        [stickers]
+        search_filter = "sticker,-static"
+        locale = "ja_JP"
+    "#;
+
+    let parms: search::Parameters = preset::parse(config, "reactions").expect("Failed to parse");
+    assert_eq!(
+        parms,
+        search::Parameters {
+            content_filter: ContentFilter::High,
+            media_filter: Some(MediaFilter::Gif | MediaFilter::TinyGif),
+            limit: 10,
+            ..Default::default()
+        }
+    );
+
+    let parms: search::Parameters = preset::parse(config, "stickers").expect("Failed to parse");
+    assert_eq!(parms.search_filter, Some(SearchFilter::NonStatic));
+    assert_eq!(
+        parms.locale,
+        Some(Locale::new(Language::Japanese, CountryCode::JP))
+    );
+    assert!(matches!(
+        preset::parse::<search::Parameters>(config, "missing"),
+        Err(preset::PresetError::Missing(_))
+    ));
+}
+
+#[test]
+fn parameters_builder() {
+    let parms = search::Parameters::builder()
+        .media_filter(MediaFilter::Gif | MediaFilter::TinyGif)
+        .limit(MAX_LIMIT)
+        .random(true)
+        .build()
+        .expect("Failed to build parameters");
+    assert_eq!(parms.limit, MAX_LIMIT);
+    assert!(parms.random);
+
+    assert_eq!(
+        search::Parameters::builder().limit(0).build(),
+        Err(ValidationError::Limit(0))
+    );
+    assert_eq!(
+        featured::Parameters::builder().limit(MAX_LIMIT + 1).build(),
+        Err(ValidationError::Limit(MAX_LIMIT + 1))
+    );
+    assert_eq!(
+        search::Parameters::builder()
+            .media_filter(MediaFilter::Gif | MediaFilter::GifTransparent)
+            .build(),
+        Err(ValidationError::TransparentWithoutSticker(
+            MediaFilter::GifTransparent.into()
+        ))
+    );
+    assert!(
+        search::Parameters::builder()
+            .media_filter(MediaFilters::DEFAULT)
+            .build()
+            .is_ok()
+    );
+    assert!((MediaFilters::DEFAULT & MediaFilters::TRANSPARENT).is_empty());
+    assert_eq!(
+        MediaFilters::DEFAULT | MediaFilters::TRANSPARENT,
+        MediaFilters::ALL
+    );
+    assert_eq!(search::Parameters::default().media_filter, None);
+    let parms = search::Parameters::builder()
+        .client_key("my_app")
+        .build()
+        .unwrap();
+    assert_eq!(parms.client_key, "my_app");
+    let parms = trending::Parameters::builder()
+        .client_key(String::from("my_app"))
+        .build()
+        .unwrap();
+    assert_eq!(parms.client_key, "my_app");
+    assert!(
+        featured::Parameters::builder()
+            .search_filter(SearchFilter::Sticker)
+            .media_filter(MediaFilter::GifTransparent)
+            .build()
+            .is_ok()
+    );
+    assert_eq!(
+        categories::Parameters::builder().client_key("").build(),
+        Err(ValidationError::EmptyClientKey)
+    );
+    assert_eq!(
+        trending::Parameters::builder().build(),
+        Ok(trending::Parameters::default())
+    );
+}
+
+#[test]
+fn cursor() {
+    let cursor = Cursor::search("happy", "CAgQ".to_string());
+    let json = serde_json::to_string(&cursor).expect("Failed to serialize");
+    assert_eq!(
+        json,
+        r#"{"endpoint":"search","query":"happy","pos":"CAgQ"}"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Cursor>(&json).ok(),
+        Some(cursor.clone())
+    );
+    assert!(!cursor.is_end());
+
+    assert_eq!(
+        cursor.position_for(Endpoint::Search, Some("happy")),
+        Ok("CAgQ")
+    );
+    assert_eq!(
+        cursor.position_for(Endpoint::Search, Some("sad")),
+        Err(ValidationError::CursorMismatch(Endpoint::Search))
+    );
+    assert_eq!(
+        featured::Parameters::builder().position(cursor).build(),
+        Err(ValidationError::CursorMismatch(Endpoint::Featured))
+    );
+
+    let end: Cursor =
+        serde_json::from_str(r#"{"endpoint":"featured","pos":""}"#).expect("Failed to parse");
+    assert!(end.is_end());
+    assert_eq!(
+        end.position_for(Endpoint::Featured, None),
+        Err(ValidationError::EndCursor)
+    );
+}
+
+#[tokio::test]
+async fn search_n() {
+    let _ = dotenv();
+    let tenor = tenor::Tenor::new(
+        env::var("API_KEY").expect("Failed to find env file"),
+        Locale::default(),
+    );
+    let response = tenor
+        .search_n("excited", 75, search::Parameters::default())
+        .await;
+
+    dbg!("{}", &response);
+    let response = response.expect("Failed to search");
+    let ids: std::collections::HashSet<_> =
+        response.results.iter().map(|object| &object.id).collect();
+    assert_eq!(response.results.len(), 75);
+    assert_eq!(ids.len(), 75);
+}
+
+#[tokio::test]
+async fn paginate() {
+    let objects = fixture_objects();
+    let tenor = Tenor::new("KEY", Locale::default());
+    let start = Cursor::search("cats", String::new());
+
+    // Serves `pages` in order, recording the position and limit of every request.
+    let run_filtered = |n: usize,
+                        filter: filter::ResultFilter,
+                        pages: Vec<(std::ops::Range<usize>, &'static str)>| {
+        let objects = &objects;
+        let tenor = &tenor;
+        let start = start.clone();
+        async move {
+            let mut calls = Vec::new();
+            let response = tenor
+                .paginate(n, None, start, &filter, |position, limit| {
+                    let index = calls.len();
+                    calls.push((position.map(|cursor| cursor.position().to_string()), limit));
+                    let (range, next) = pages[index].clone();
+                    std::future::ready(Ok(search::Response {
+                        results: objects[range].to_vec(),
+                        next: Cursor::search("cats", next.to_string()),
+                    }))
+                })
+                .await
+                .unwrap();
+            (response, calls)
+        }
+    };
+    let run = |n: usize, pages: Vec<(std::ops::Range<usize>, &'static str)>| {
+        run_filtered(n, filter::ResultFilter::new(), pages)
+    };
+    let ids = |response: &search::Response| -> Vec<String> {
+        response
+            .results
+            .iter()
+            .map(|object| object.id.clone())
+            .collect()
+    };
+    let expected = |range: std::ops::Range<usize>| -> Vec<String> {
+        objects[range]
+            .iter()
+            .map(|object| object.id.clone())
+            .collect()
+    };
+
+    let (response, calls) = run(0, vec![]).await;
+    assert!(response.results.is_empty());
+    assert!(calls.is_empty());
+
+    // Duplicates across pages are dropped and later pages ask only for what is missing.
+    let (response, calls) = run(8, vec![(0..5, "5"), (3..7, "7"), (5..11, "11")]).await;
+    assert_eq!(ids(&response), expected(0..8));
+    assert_eq!(
+        calls,
+        [
+            (None, 8),
+            (Some("5".to_string()), 3),
+            (Some("7".to_string()), 1)
+        ]
+    );
+    assert_eq!(response.next.position(), "11");
+
+    // An empty `next` ends the search early.
+    let (response, calls) = run(20, vec![(0..5, "5"), (5..8, "")]).await;
+    assert_eq!(ids(&response), expected(0..8));
+    assert_eq!(calls.len(), 2);
+    assert!(response.next.is_end());
+
+    // A page without new results stops instead of looping.
+    let (response, calls) = run(20, vec![(0..5, "5"), (0..5, "5"), (5..10, "10")]).await;
+    assert_eq!(ids(&response), expected(0..5));
+    assert_eq!(calls.len(), 2);
+
+    // With a filter every request asks for a full page and the extra matches are dropped.
+    let silent = filter::ResultFilter::new().has_audio(false);
+    let (response, calls) = run_filtered(8, silent.clone(), vec![(0..5, "5"), (5..30, "30")]).await;
+    let matching: Vec<String> = silent
+        .apply(objects[0..30].to_vec())
+        .into_iter()
+        .map(|object| object.id)
+        .take(8)
+        .collect();
+    assert_eq!(matching.len(), 8);
+    assert_eq!(ids(&response), matching);
+    assert_eq!(
+        calls,
+        [(None, MAX_LIMIT), (Some("5".to_string()), MAX_LIMIT)]
+    );
+}
+
+/// The results in the bench fixture, read with the owned model.
+fn fixture_objects() -> Vec<search::ResponseObject> {
+    let body: serde_json::Value =
+        serde_json::from_str(include_str!("../benches/fixtures/search.json")).unwrap();
+    serde_json::from_value(body["results"].clone()).expect("Failed to parse")
+}
+
+/// A result without any media formats, for tests that don't hit the api.
+fn response_object(id: &str) -> search::ResponseObject {
+    search::ResponseObject {
+        created: 0.0,
+        hasaudio: false,
+        id: id.to_string(),
+        media_formats: search::ContentFormats {
+            gifpreview: None,
+            gif: None,
+            mediumgif: None,
+            tinygif: None,
+            nanogif: None,
+            mp4: None,
+            loopedmp4: None,
+            tinymp4: None,
+            nanomp4: None,
+            webm: None,
+            tinywebm: None,
+            nanowebm: None,
+            webp: None,
+            tinygifpreview: None,
+            nanogifpreview: None,
+        },
+        tags: Vec::new(),
+        title: String::new(),
+        content_description: String::new(),
+        itemurl: String::new(),
+        flags: Vec::new(),
+        url: String::new(),
+    }
+}
+
+#[test]
+fn batch_interleave() {
+    let response = |ids: &[&str]| search::Response {
+        results: ids.iter().map(|id| response_object(id)).collect(),
+        next: Cursor::search("happy", String::new()),
+    };
+    let batch = search::Batch::new(vec![
+        ("happy".to_string(), Ok(response(&["1", "2", "3"]))),
+        (
+            "sad".to_string(),
+            Err(Error::Io(std::sync::Arc::new(std::io::Error::other(
+                "offline",
+            )))),
+        ),
+        ("lol".to_string(), Ok(response(&["4", "1", "5"]))),
+    ]);
+
+    assert!(batch.get("sad").is_some_and(|result| result.is_err()));
+    let ids: Vec<_> = batch
+        .interleave()
+        .into_iter()
+        .map(|object| object.id)
+        .collect();
+    assert_eq!(ids, ["1", "4", "2", "3", "5"]);
+}
+
+#[test]
+fn result_filter() {
+    let gif = |id: &str, dimensions: [i64; 2], duration: f64, size: i64| {
+        let mut object = response_object(id);
+        object.media_formats.gif = Some(search::Gif {
+            url: String::new(),
+            duration,
+            preview: String::new(),
+            dimensions: dimensions.to_vec(),
+            size,
+        });
+        object
+    };
+    let mut loud = gif("loud", [200, 100], 2.0, 500);
+    loud.hasaudio = true;
+    let objects = vec![
+        gif("wide", [300, 100], 1.0, 100),
+        gif("square", [100, 100], 4.0, 100),
+        gif("huge", [200, 100], 1.5, 5_000),
+        loud,
+        response_object("empty"),
+    ];
+    let ids = |filter: filter::ResultFilter| -> Vec<String> {
+        filter
+            .apply(objects.clone())
+            .into_iter()
+            .map(|object| object.id)
+            .collect()
+    };
+
+    assert_eq!(ids(filter::ResultFilter::new()).len(), objects.len());
+    assert_eq!(
+        ids(filter::ResultFilter::new().min_aspect_ratio(1.5)),
+        ["wide", "huge", "loud"]
+    );
+    assert_eq!(
+        ids(filter::ResultFilter::new().max_aspect_ratio(1.0)),
+        ["square"]
+    );
+    assert_eq!(
+        ids(filter::ResultFilter::new()
+            .min_duration(std::time::Duration::from_secs(1))
+            .max_duration(std::time::Duration::from_secs(3))),
+        ["wide", "huge", "loud"]
+    );
+    assert_eq!(
+        ids(filter::ResultFilter::new()
+            .max_size(MediaFilter::Gif, 1_000)
+            .has_audio(false)),
+        ["wide", "square"]
+    );
+    assert_eq!(
+        ids(filter::ResultFilter::new().require(MediaFilter::Gif | MediaFilter::Mp4)),
+        Vec::<String>::new()
+    );
+
+    // Real results include the still `gifpreview` and the repeating `loopedmp4`, neither is the clip's length.
+    let objects = fixture_objects();
+    let between = filter::ResultFilter::new()
+        .min_duration(std::time::Duration::from_secs(2))
+        .max_duration(std::time::Duration::from_secs(5))
+        .apply(objects.clone());
+    let expected: Vec<_> = objects
+        .iter()
+        .filter(|object| {
+            let duration = object.media_formats.gif.as_ref().unwrap().duration;
+            (2.0..=5.0).contains(&duration)
+        })
+        .collect();
+    assert!(!between.is_empty() && between.len() < objects.len());
+    assert_eq!(between.iter().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn blocklist() {
+    let blocklist = moderation::Blocklist::new()
+        .words(["scary", "blood bath"])
+        .pattern(r"(?i)^spoo+ky$")
+        .expect("Failed to compile pattern")
+        .check_queries(true);
+
+    assert_eq!(
+        blocklist.matches("A SCARY movie"),
+        Some(r"(?i)(?:^|\W)scary(?:\W|$)")
+    );
+    assert!(blocklist.matches("scarygood").is_none());
+    assert!(blocklist.matches("a blood bath").is_some());
+    assert!(blocklist.blocks_query("Spoooky"));
+    assert!(!blocklist.blocks_query("happy"));
+
+    let mut tagged = response_object("tagged");
+    tagged.tags = vec!["cute".to_string(), "Scary".to_string()];
+    let mut flagged = response_object("flagged");
+    flagged.flags = vec![serde_json::json!("blood bath")];
+    let mut results = vec![tagged, flagged, response_object("clean")];
+    blocklist.retain_results(&mut results);
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].id, "clean");
+
+    let mut terms = vec!["spooky".to_string(), "dance".to_string()];
+    blocklist.retain_terms(&mut terms);
+    assert_eq!(terms, ["dance"]);
+
+    // Words may start or end with symbols, like category names.
+    let symbols = moderation::Blocklist::new().words(["#nsfw", "c++"]);
+    assert!(symbols.matches("#nsfw").is_some());
+    assert!(symbols.matches("see #NSFW.").is_some());
+    assert!(symbols.matches("I like c++").is_some());
+    assert!(symbols.matches("#nsfwx").is_none());
+    assert!(symbols.matches("nsfw").is_none());
+
+    // Blank config lines don't block everything.
+    let blank = moderation::Blocklist::new().words(["", "  ", "\t"]);
+    assert!(blank.matches("anything at all").is_none());
+    let mut tags = vec![categories::Tag {
+        searchterm: "excited".to_string(),
+        path: "/v2/search?q=excited".to_string(),
+        image: "https://media.tenor.com/excited.gif".to_string(),
+        name: "#excited".to_string(),
+    }];
+    blank.retain_tags(&mut tags);
+    assert_eq!(tags.len(), 1);
+}
+
+#[test]
+fn random_session() {
+    let mut mp4 = response_object("mp4");
+    mp4.media_formats.mp4 = Some(search::Mp4 {
+        url: String::new(),
+        duration: 1.0,
+        preview: String::new(),
+        dimensions: vec![100, 100],
+        size: 100,
+    });
+    let results = vec![response_object("plain"), mp4];
+    let parms = random::Parameters::builder()
+        .format(MediaFilter::Gif)
+        .format(MediaFilter::Mp4)
+        .build()
+        .expect("Failed to build parameters");
+
+    let mut session = random::Session::new();
+    let chosen = session
+        .choose(&results, Some(&parms))
+        .expect("Nothing chosen");
+    assert_eq!(chosen.id, "mp4");
+    assert_eq!(parms.preferred_format(chosen), Some(MediaFilter::Mp4));
+    session.record(chosen);
+
+    assert!(session.choose(&results, Some(&parms)).is_none());
+    assert_eq!(
+        session
+            .choose(&results, None)
+            .map(|object| object.id.as_str()),
+        Some("plain")
+    );
+}
+
+#[tokio::test]
+async fn random() {
+    let _ = dotenv();
+    let tenor = tenor::Tenor::new(
+        env::var("API_KEY").expect("Failed to find env file"),
+        Locale::default(),
+    );
+    let mut session = random::Session::new();
+    let parms = random::Parameters::builder()
+        .format(MediaFilter::Gif)
+        .build()
+        .expect("Failed to build parameters");
+    let first = tenor
+        .random_with_parameters("happy", parms.clone(), &mut session)
+        .await;
+
+    dbg!("{}", &first);
+    let first = first.expect("Failed to search").expect("No result");
+    let second = tenor
+        .random_with_parameters("happy", parms, &mut session)
+        .await
+        .expect("Failed to search")
+        .expect("No result");
+    assert_ne!(first.id, second.id);
+}
+
+#[test]
+fn error_context() {
+    let tenor = tenor::Tenor::new("SECRET", Locale::default());
+    let url = tenor::redact_url(&tenor.search_url("happy", None));
+    assert_eq!(
+        url,
+        "https://tenor.googleapis.com/v2/search?q=happy&key=REDACTED&country=US&locale=en_US"
+    );
+
+    let status = |status: StatusCode, body: &str| Error::Status {
+        context: RequestContext {
+            endpoint: "search".into(),
+            url: url.clone(),
+            status: Some(status),
+        },
+        body: body.to_string(),
+    };
+    let bad_key = status(
+        StatusCode::BAD_REQUEST,
+        r#"{"error":{"code":400,"message":"API key not valid. Please pass a valid API key."}}"#,
+    );
+    assert!(bad_key.is_auth());
+    assert!(!bad_key.is_retryable());
+    assert!(bad_key.to_string().contains("returned 400 Bad Request"));
+    assert!(!bad_key.to_string().contains("SECRET"));
+
+    assert!(status(StatusCode::TOO_MANY_REQUESTS, "").is_rate_limited());
+    assert!(status(StatusCode::TOO_MANY_REQUESTS, "").is_retryable());
+    assert!(status(StatusCode::BAD_GATEWAY, "").is_retryable());
+    assert!(status(StatusCode::NOT_FOUND, "").is_not_found());
+    assert!(!status(StatusCode::BAD_REQUEST, "limit").is_auth());
+    assert_eq!(Error::BlockedQuery("scary".to_string()).status(), None);
+}
+
+#[tokio::test]
+async fn search_with_meta() {
+    let _ = dotenv();
+    let key = env::var("API_KEY").expect("Failed to find env file");
+    let tenor = tenor::Tenor::new(key.clone(), Locale::default());
+    let response = tenor
+        .search_with_meta("happy", search::Parameters::default())
+        .await;
+
+    dbg!("{}", &response);
+    let tracked = response.expect("Failed to search");
+    assert!(tracked.meta.status.is_success());
+    assert!(tracked.meta.bytes > 0);
+    assert!(!tracked.meta.url.contains(&key));
+    assert!(!tracked.value.results.is_empty());
+}
+
+#[tokio::test]
+async fn search_raw() {
+    let _ = dotenv();
+    let tenor = tenor::Tenor::new(
+        env::var("API_KEY").expect("Failed to find env file"),
+        Locale::default(),
+    );
+    let response = tenor
+        .search_raw("happy", search::Parameters::default())
+        .await;
+
+    dbg!("{}", &response);
+    let response = response.expect("Failed to search");
+    assert!(response["results"].is_array());
+    assert!(response["next"].is_string());
+}
+
+#[tokio::test]
+async fn search_many() {
+    let _ = dotenv();
+    let tenor = tenor::Tenor::new(
+        env::var("API_KEY").expect("Failed to find env file"),
+        Locale::default(),
+    );
+    let batch = tenor
+        .search_many(["happy", "sad", "lol"], search::Parameters::default(), 2)
+        .await;
+
+    dbg!("{}", &batch);
+    assert_eq!(batch.len(), 3);
+    assert!(batch.iter().all(|(_, result)| result.is_ok()));
+    assert!(!batch.interleave().is_empty());
+}
+
+#[tokio::test]
+async fn categories_with_previews() {
+    let _ = dotenv();
+    let tenor = tenor::Tenor::new(
+        env::var("API_KEY").expect("Failed to find env file"),
+        Locale::default(),
+    );
+    let parms = search::Parameters {
+        limit: 1,
+        ..Default::default()
+    };
+    let previews = tenor
+        .categories_with_previews(
+            categories::Type::Trending,
+            categories::Parameters::default(),
+            parms,
+            4,
+        )
+        .await;
+
+    dbg!("{}", &previews);
+    let previews = previews.expect("Failed to fetch categories");
+    assert!(!previews.is_empty());
+    assert!(previews.iter().all(|preview| preview.results.is_ok()));
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn trending_terms_with_previews_offline() {
+    let transport = transport::Transport::from_fn(|request: transport::TenorRequest| async move {
+        let (status, body) = match request.endpoint.as_ref() {
+            "trending_terms" => (
+                StatusCode::OK,
+                r#"{"locale":"en","results":["cats","dogs"]}"#,
+            ),
+            _ if request.url.contains("q=dogs") => (StatusCode::INTERNAL_SERVER_ERROR, "oops"),
+            _ => (
+                StatusCode::OK,
+                include_str!("../benches/fixtures/search.json"),
+            ),
+        };
+        Ok(transport::TenorResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: body.to_string(),
+        })
+    });
+    let tenor = Tenor::with_transport("KEY", Locale::default(), transport);
+
+    let previews = tenor
+        .trending_terms_with_previews(Default::default(), 5, None, 1)
+        .await
+        .unwrap();
+    assert_eq!(previews.len(), 2);
+    assert_eq!(previews[0].term, "cats");
+    assert!(previews[0].results.is_ok());
+    assert_eq!(
+        previews[1].results.as_ref().unwrap_err().status(),
+        Some(StatusCode::INTERNAL_SERVER_ERROR)
+    );
+
+    for limit in [0, MAX_LIMIT + 1] {
+        assert!(matches!(
+            tenor
+                .trending_terms_with_previews(Default::default(), limit, None, 1)
+                .await,
+            Err(Error::Validation(ValidationError::Limit(_)))
+        ));
+    }
+}
+
+#[cfg(feature = "test-util")]
+#[tokio::test]
+async fn get_json_endpoint() {
+    let transport = transport::Transport::from_fn(|request: transport::TenorRequest| async move {
+        let status = match request.endpoint.as_ref() {
+            "autocomplete" => StatusCode::OK,
+            _ => StatusCode::NOT_FOUND,
+        };
+        Ok(transport::TenorResponse {
+            status,
+            headers: HeaderMap::new(),
+            body: r#"{"results":["happy birthday"]}"#.to_string(),
+        })
+    });
+    let tenor = Tenor::with_transport("KEY", Locale::default(), transport);
+
+    // Endpoint names read at runtime don't need to be `'static`.
+    let endpoint = String::from("autocomplete");
+    let value = tenor
+        .get_json(&endpoint, &[("q", "happy b")])
+        .await
+        .unwrap();
+    assert_eq!(value["results"][0], "happy birthday");
+
+    let endpoint = String::from("posts");
+    let err = tenor.get_json(&endpoint, &[]).await.unwrap_err();
+    assert_eq!(err.context().unwrap().endpoint, "posts");
+}
+
+#[tokio::test]
+async fn trending_terms_with_previews() {
+    let _ = dotenv();
+    let tenor = tenor::Tenor::new(
+        env::var("API_KEY").expect("Failed to find env file"),
+        Locale::default(),
+    );
+    let parms = trending::Parameters {
+        limit: 5,
+        ..Default::default()
+    };
+    let previews = tenor
+        .trending_terms_with_previews(parms, 1, Some(MediaFilter::TinyGif.into()), 4)
+        .await;
+
+    dbg!("{}", &previews);
+    let previews = previews.expect("Failed to fetch trending terms");
+    assert_eq!(previews.len(), 5);
+    assert!(previews.iter().all(|preview| {
+        preview
+            .results
+            .as_ref()
+            .is_ok_and(|response| response.results.len() <= 1)
+    }));
+}
@@ -0,0 +1,36 @@
+use std::time::Duration;
+
+use reqwest::{StatusCode, header::HeaderMap};
+
+/// A response together with how it was fetched, returned by the `_with_meta` methods.
+#[derive(Debug, Clone)]
+pub struct Tracked<T> {
+    pub value: T,
+    pub meta: ResponseMeta,
+}
+
+impl<T> Tracked<T> {
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    pub(crate) fn map<U>(self, f: impl FnOnce(T) -> U) -> Tracked<U> {
+        Tracked {
+            value: f(self.value),
+            meta: self.meta,
+        }
+    }
+}
+
+/// What Tenor answered besides the body.
+#[derive(Debug, Clone)]
+pub struct ResponseMeta {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    /// Time from sending the request until the whole body was read.
+    pub elapsed: Duration,
+    /// The request url with the api key replaced by `REDACTED`.
+    pub url: String,
+    /// Size of the body in bytes.
+    pub bytes: usize,
+}
@@ -0,0 +1,185 @@
+//! The http layer under [`Tenor`](crate::Tenor), exposed so requests can go through middleware.
+//!
+#![cfg_attr(
+    feature = "tower",
+    doc = "With the `tower` feature [`HttpService`] is the default pipeline as a `tower::Service`,
+wrap it in your own layers and hand the result to [`Tenor::with_service`](crate::Tenor::with_service)."
+)]
+#![cfg_attr(
+    not(feature = "tower"),
+    doc = "With the `tower` feature `HttpService` is the default pipeline as a `tower::Service`,
+wrap it in your own layers and hand the result to `Tenor::with_service`."
+)]
+
+use std::{borrow::Cow, fmt, future::Future, sync::Arc};
+
+use futures::future::BoxFuture;
+use reqwest::{StatusCode, header::HeaderMap};
+
+use crate::{Error, RequestContext};
+
+/// Errors a transport may fail with, matching `tower::BoxError`.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync>;
+
+/// A GET request to the Tenor api, the url includes the api key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TenorRequest {
+    /// Name of the endpoint, for example `search`.
+    pub endpoint: Cow<'static, str>,
+    pub url: String,
+}
+
+/// Whatever Tenor answered, error statuses included.
+#[derive(Debug, Clone)]
+pub struct TenorResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: String,
+}
+
+/// Sends the request with `client`, errors never include the url since it holds the api key.
+async fn send(client: reqwest::Client, request: TenorRequest) -> Result<TenorResponse, BoxError> {
+    let response = client
+        .get(request.url)
+        .send()
+        .await
+        .map_err(reqwest::Error::without_url)?;
+    let status = response.status();
+    let headers = response.headers().clone();
+    let body = response.text().await.map_err(reqwest::Error::without_url)?;
+
+    Ok(TenorResponse {
+        status,
+        headers,
+        body,
+    })
+}
+
+type SendFn =
+    dyn Fn(TenorRequest) -> BoxFuture<'static, Result<TenorResponse, BoxError>> + Send + Sync;
+
+/// What [`Tenor`](crate::Tenor) sends its requests through.
+#[derive(Clone)]
+pub(crate) struct Transport(Arc<SendFn>);
+
+impl Transport {
+    pub(crate) fn from_fn<F, Fut>(send: F) -> Self
+    where
+        F: Fn(TenorRequest) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<TenorResponse, BoxError>> + Send + 'static,
+    {
+        Self(Arc::new(move |request| Box::pin(send(request))))
+    }
+
+    pub(crate) fn http(client: reqwest::Client) -> Self {
+        Self::from_fn(move |request| send(client.clone(), request))
+    }
+
+    #[cfg(feature = "tower")]
+    pub(crate) fn service<S>(service: S) -> Self
+    where
+        S: tower::Service<TenorRequest, Response = TenorResponse> + Clone + Send + 'static,
+        S::Error: Into<BoxError>,
+        S::Future: Send,
+    {
+        // Services such as `BoxCloneService` aren't `Sync`, the mutex is only held to clone one per request.
+        let service = std::sync::Mutex::new(service);
+        Self(Arc::new(move |request| {
+            let mut service = service
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .clone();
+            Box::pin(async move {
+                std::future::poll_fn(|cx| service.poll_ready(cx))
+                    .await
+                    .map_err(Into::into)?;
+                service.call(request).await.map_err(Into::into)
+            })
+        }))
+    }
+
+    pub(crate) async fn send(&self, request: TenorRequest) -> Result<TenorResponse, BoxError> {
+        (self.0)(request).await
+    }
+}
+
+/// Why a replaying [`Cassette`](crate::testing::Cassette) could not answer, surfaced as [`Error::Replay`].
+#[cfg(feature = "test-util")]
+#[derive(Debug)]
+pub(crate) struct ReplayError(pub(crate) String);
+
+#[cfg(feature = "test-util")]
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(feature = "test-util")]
+impl std::error::Error for ReplayError {}
+
+/// Turns a transport failure into the matching [`Error`] variant.
+pub(crate) fn into_error(source: BoxError, context: RequestContext) -> Error {
+    let source = match source.downcast::<reqwest::Error>() {
+        Ok(source) => {
+            return Error::Send {
+                context,
+                source: Arc::new(*source),
+            };
+        }
+        Err(source) => source,
+    };
+    #[cfg(feature = "test-util")]
+    let source = match source.downcast::<ReplayError>() {
+        Ok(replay) => {
+            return Error::Replay {
+                context,
+                message: replay.0,
+            };
+        }
+        Err(source) => source,
+    };
+
+    Error::Transport {
+        context,
+        source: Arc::from(source),
+    }
+}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Transport")
+    }
+}
+
+/// The default request pipeline as a `tower::Service`, sending every request with a [`reqwest::Client`].
+#[cfg(feature = "tower")]
+#[derive(Debug, Clone, Default)]
+pub struct HttpService {
+    client: reqwest::Client,
+}
+
+#[cfg(feature = "tower")]
+impl HttpService {
+    pub fn new(client: reqwest::Client) -> Self {
+        Self { client }
+    }
+}
+
+#[cfg(feature = "tower")]
+impl tower::Service<TenorRequest> for HttpService {
+    type Response = TenorResponse;
+    type Error = BoxError;
+    type Future = BoxFuture<'static, Result<TenorResponse, BoxError>>;
+
+    fn poll_ready(
+        &mut self,
+        _: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        std::task::Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: TenorRequest) -> Self::Future {
+        Box::pin(send(self.client.clone(), request))
+    }
+}
@@ -1,11 +1,80 @@
 use serde::{Deserialize, Serialize};
 
-use crate::Limit;
+use crate::{CountryCode, DEFAULT_LIMIT, Error, Limit, Locale, ValidationError, search};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct Parameters {
     pub client_key: String,
     pub limit: Limit,
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    pub locale: Option<Locale>,
+    /// Overrides the country of the locale for this request.
+    pub country: Option<CountryCode>,
+}
+
+impl Default for Parameters {
+    fn default() -> Self {
+        Self {
+            client_key: "my_test_app".to_string(),
+            limit: DEFAULT_LIMIT,
+            locale: None,
+            country: None,
+        }
+    }
+}
+
+impl Parameters {
+    /// Returns a builder that validates the parameters when built.
+    pub fn builder() -> ParametersBuilder {
+        ParametersBuilder::default()
+    }
+
+    /// Checks the parameters against Tenor's documented constraints.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        ValidationError::check_client_key(&self.client_key)?;
+        ValidationError::check_limit(self.limit)
+    }
+}
+
+/// Builder for [`Parameters`], starting from the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ParametersBuilder {
+    parms: Parameters,
+}
+
+impl ParametersBuilder {
+    #[must_use]
+    pub fn client_key(mut self, client_key: impl Into<String>) -> Self {
+        self.parms.client_key = client_key.into();
+        self
+    }
+
+    #[must_use]
+    pub fn limit(mut self, limit: Limit) -> Self {
+        self.parms.limit = limit;
+        self
+    }
+
+    /// Overrides the [`Tenor`](crate::Tenor) instance's locale for this request.
+    #[must_use]
+    pub fn locale(mut self, locale: Locale) -> Self {
+        self.parms.locale = Some(locale);
+        self
+    }
+
+    /// Overrides the country of the locale for this request.
+    #[must_use]
+    pub fn country(mut self, country: CountryCode) -> Self {
+        self.parms.country = Some(country);
+        self
+    }
+
+    /// Returns the parameters if they pass [`Parameters::validate`].
+    pub fn build(self) -> Result<Parameters, ValidationError> {
+        self.parms.validate()?;
+        Ok(self.parms)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -13,3 +82,11 @@ pub struct Response {
     pub locale: String,
     pub results: Vec<String>,
 }
+
+/// A trending term along with a few of its search results.
+#[derive(Debug, Clone)]
+pub struct Preview {
+    pub term: String,
+    /// Results of searching for the term.
+    pub results: Result<search::Response, Error>,
+}